@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use isbot::{Bots, CachedBots};
+
+const BROWSER_TEST_PATTERNS: &str = include_str!("../fixtures/browsers.txt");
+const BOT_TEST_PATTERNS: &str = include_str!("../fixtures/bots.txt");
+
+const DISTINCT_USER_AGENTS: usize = 50;
+const REPEATS: usize = 100000;
+
+/// Repeats a mix of 50 bot and browser user-agents, like the long tail of real traffic
+fn get_repeated_user_agents() -> Vec<&'static str> {
+    let distinct = BOT_TEST_PATTERNS
+        .lines()
+        .take(DISTINCT_USER_AGENTS / 2)
+        .chain(BROWSER_TEST_PATTERNS.lines().take(DISTINCT_USER_AGENTS / 2))
+        .collect::<Vec<&str>>();
+    distinct.iter().copied().cycle().take(REPEATS).collect()
+}
+
+fn benchmark_repeated_user_agents(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Repeated");
+    group.sample_size(10);
+
+    group.bench_function("Bots::is_bot", |b| {
+        let bots = Bots::default();
+        let user_agents = get_repeated_user_agents();
+
+        b.iter(|| {
+            for user_agent in &user_agents {
+                bots.is_bot(black_box(user_agent));
+            }
+        })
+    });
+
+    group.bench_function("CachedBots::is_bot", |b| {
+        let bots = CachedBots::new(Bots::default(), 1000);
+        let user_agents = get_repeated_user_agents();
+
+        b.iter(|| {
+            for user_agent in &user_agents {
+                bots.is_bot(black_box(user_agent));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_repeated_user_agents);
+criterion_main!(benches);
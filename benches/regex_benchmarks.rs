@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use isbot::Bots;
-use regex::RegexSet;
+use regex::{Regex, RegexSet};
 
 const BROWSER_TEST_PATTERNS: &str = include_str!("../fixtures/browsers.txt");
 const BOT_PATTERNS: &str = include_str!("../src/bot_regex_patterns.txt");
@@ -28,6 +28,62 @@ fn benchmark_browser_user_agents(c: &mut Criterion) {
         })
     });
 
+    // Longer than the stack buffer used by `Bots::is_bot`, like user-agents with many extension tokens
+    group.bench_function("Bot::is_bot long", |b| {
+        let bots = Bots::default();
+        let browser_user_agents = get_browser_user_agents()
+            .into_iter()
+            .map(|user_agent| format!("{} {}", user_agent, "Extension/1.0 ".repeat(20)))
+            .collect::<Vec<String>>();
+
+        b.iter(|| {
+            for user_agent in &browser_user_agents {
+                bots.is_bot(black_box(user_agent));
+            }
+        })
+    });
+
+    group.bench_function("Bot::is_bot_batch", |b| {
+        let bots = Bots::default();
+        let browser_user_agents = get_browser_user_agents();
+
+        b.iter(|| {
+            bots.is_bot_batch(
+                browser_user_agents
+                    .iter()
+                    .map(|user_agent| black_box(user_agent.as_str())),
+            )
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    group.bench_function("Bot::is_bot_batch_par", |b| {
+        let bots = Bots::default();
+        let browser_user_agents = get_browser_user_agents();
+        let browser_user_agents = browser_user_agents
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+
+        b.iter(|| bots.is_bot_batch_par(black_box(&browser_user_agents)))
+    });
+
+    group.bench_function("Regex", |b| {
+        let bot_patterns = BOT_PATTERNS
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<&str>>();
+        let bot_patterns_regex =
+            Regex::new(&bot_patterns.join("|")).expect("Invalid regular expression");
+        let browser_user_agents = get_browser_user_agents();
+
+        b.iter(|| {
+            for user_agent in &browser_user_agents {
+                bot_patterns_regex.is_match(black_box(user_agent));
+            }
+        })
+    });
+
     group.bench_function("RegexSet", |b| {
         let bot_patterns = BOT_PATTERNS
             .lines()
@@ -43,6 +99,38 @@ fn benchmark_browser_user_agents(c: &mut Criterion) {
         })
     });
 
+    // Lowercase each user-agent like `Bots::is_bot`, so the engines are compared on equal terms
+    group.bench_function("Regex lowercase", |b| {
+        let bot_patterns = BOT_PATTERNS
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<&str>>();
+        let bot_patterns_regex =
+            Regex::new(&bot_patterns.join("|")).expect("Invalid regular expression");
+        let browser_user_agents = get_browser_user_agents();
+
+        b.iter(|| {
+            for user_agent in &browser_user_agents {
+                bot_patterns_regex.is_match(&black_box(user_agent).to_ascii_lowercase());
+            }
+        })
+    });
+
+    group.bench_function("RegexSet lowercase", |b| {
+        let bot_patterns = BOT_PATTERNS
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<&str>>();
+        let bot_patterns_regex = RegexSet::new(&bot_patterns).expect("Invalid regular expression");
+        let browser_user_agents = get_browser_user_agents();
+
+        b.iter(|| {
+            for user_agent in &browser_user_agents {
+                bot_patterns_regex.is_match(&black_box(user_agent).to_ascii_lowercase());
+            }
+        })
+    });
+
     group.finish();
 }
 
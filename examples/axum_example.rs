@@ -0,0 +1,130 @@
+use isbot::{BotFilterLayer, Bots};
+
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+async fn index() -> &'static str {
+    "Home"
+}
+
+async fn login() -> &'static str {
+    "Login"
+}
+
+/// Rejects bots on every route with the layer, `403 Forbidden` by default
+fn app(bots: Arc<Bots>) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/login", get(login))
+        .layer(BotFilterLayer::new(bots))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+    axum::serve(listener, app(Arc::new(Bots::default()))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use http::{
+        header::{RETRY_AFTER, USER_AGENT},
+        Request, StatusCode,
+    };
+    use isbot::{BotCategory, BotPolicy};
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    const VALID_BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/96.0.4664.110 Safari/537.36";
+    const KNOWN_BOT_USER_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 9_1 like Mac OS X) AppleWebKit/601.1.46 (KHTML, like Gecko) Version/9.0 Mobile/13B143 Safari/601.1 (compatible; AdsBot-Google-Mobile; +http://www.google.com/mobile/adsbot.html)";
+    const CHROME_LIGHTHOUSE_BOT_USER_AGENT: &str = "Mozilla/5.0 (Linux; Android 7.0; Moto G (4)) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/84.0.4143.7 Mobile Safari/537.36 Chrome-Lighthouse";
+
+    fn request(uri: &str, user_agent: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(USER_AGENT, user_agent)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_browser() {
+        let response = app(Arc::new(Bots::default()))
+            .oneshot(request("/", VALID_BROWSER_USER_AGENT))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "Home");
+    }
+
+    #[tokio::test]
+    async fn test_known_bot() {
+        let response = app(Arc::new(Bots::default()))
+            .oneshot(request("/login", KNOWN_BOT_USER_AGENT))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exclude_known_bot() {
+        let mut bots = Bots::default();
+        bots.remove(&["Chrome-Lighthouse"]); // Example: remove Chrome Lighthouse for performance testing
+
+        let response = app(Arc::new(bots))
+            .oneshot(request("/", CHROME_LIGHTHOUSE_BOT_USER_AGENT))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_custom_status() {
+        let app = Router::new()
+            .route("/", get(index))
+            .layer(BotFilterLayer::new(Arc::new(Bots::default())).status(StatusCode::NOT_FOUND));
+        let response = app
+            .oneshot(request("/", KNOWN_BOT_USER_AGENT))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_policy() {
+        let policy = BotPolicy::new(Bots::default())
+            .rate_limit(BotCategory::Archiver, Duration::from_secs(60));
+        let app = Router::new()
+            .route("/", get(index))
+            .layer(BotFilterLayer::with_policy(Arc::new(policy)));
+
+        let archiver =
+            "Mozilla/5.0 (compatible; archive.org_bot +http://archive.org/details/archive.org_bot)";
+        let response = app.clone().oneshot(request("/", archiver)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "60");
+
+        let googlebot = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+        let response = app.clone().oneshot(request("/", googlebot)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let headless = "Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36";
+        let response = app.oneshot(request("/", headless)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_missing_user_agent() {
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = app(Arc::new(Bots::default()))
+            .oneshot(request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -40,7 +40,7 @@ impl<'r> FromRequest<'r> for Browser {
             if request
                 .rocket()
                 .state::<AppState>()
-                .map_or(false, |state| state.bots.is_bot(user_agent))
+                .is_some_and(|state| state.bots.is_bot(user_agent))
             {
                 return Outcome::Failure((Status::Forbidden, ()));
             }
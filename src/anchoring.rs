@@ -0,0 +1,148 @@
+use crate::Bots;
+
+/// Anchoring applied to patterns appended with [`Bots::append`] or [`BotsBuilder::append`](crate::BotsBuilder::append)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Anchoring {
+    /// Patterns match anywhere in the user-agent
+    #[default]
+    None,
+    /// Patterns only match at the start of the user-agent, like `^googlebot`
+    Start,
+    /// Patterns only match at the end of a user-agent token: followed by `/`, `;`, `)`, `,`,
+    /// whitespace, or the end of the user-agent, so `bot` matches `Googlebot/2.1` but not `robot-free`.
+    /// Nothing is required before the pattern, see [`Anchoring::Boundary`] for whole words
+    TokenEnd,
+    /// Patterns only match as whole words, with a word boundary `\b` on both sides, so `bot` matches
+    /// `Bot/1.0` but neither `robotics` nor `Googlebot`
+    Boundary,
+}
+
+impl Anchoring {
+    /// Returns the pattern wrapped with the anchoring.
+    pub(crate) fn apply(self, pattern: &str) -> String {
+        match self {
+            Anchoring::None => pattern.to_string(),
+            Anchoring::Start => format!("^(?:{})", pattern),
+            Anchoring::TokenEnd => format!(r"(?:{})(?:$|[/;),\s])", pattern),
+            Anchoring::Boundary => format!(r"\b(?:{})\b", pattern),
+        }
+    }
+}
+
+impl Bots {
+    /// Sets the anchoring applied to patterns passed to future [`Bots::append`] calls.
+    ///
+    /// Existing patterns and patterns appended with [`Bots::append_raw`] are not changed. An anchored
+    /// pattern is stored with its anchoring, so pass the anchored form to [`Bots::remove`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Anchoring, Bots};
+    ///
+    /// let mut bots = Bots::empty();
+    /// bots.set_default_append_anchoring(Anchoring::TokenEnd);
+    /// bots.append(&["bot"]);
+    ///
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// assert!(!bots.is_bot("robot-free zone"));
+    /// ```
+    pub fn set_default_append_anchoring(&mut self, anchoring: Anchoring) {
+        self.append_anchoring = anchoring;
+    }
+
+    /// Appends bot user-agent regular expressions patterns without the default append anchoring.
+    ///
+    /// Duplicates are ignored.
+    pub fn append_raw(&mut self, bots: &[&str]) {
+        for bot in bots.iter().filter(|bot| !bot.trim().is_empty()) {
+            self.user_agent_patterns.insert(self.normalize_pattern(bot));
+        }
+        self.update_regex()
+    }
+
+    /// Appends bot user-agent regular expressions that only match as whole words, wrapping each
+    /// pattern with `\b` as in [`Anchoring::Boundary`].
+    ///
+    /// Plain [`Bots::append`] matches a pattern anywhere in the user-agent, so a short pattern such as
+    /// `bot` or `me` also matches inside unrelated words and can flag real browsers. An anchored
+    /// pattern only matches when it is not preceded or followed by a letter, digit, or `_`, so it also
+    /// no longer matches as part of a longer token such as `Googlebot`. The pattern is stored with its
+    /// anchoring, so pass the anchored form, such as `\b(?:bot)\b`, to [`Bots::remove`]. Duplicates
+    /// are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::empty();
+    /// bots.append_anchored(&["bot"]);
+    ///
+    /// assert!(bots.is_bot("Mozilla/5.0 (compatible; Bot/1.0)"));
+    /// assert!(!bots.is_bot("Mozilla/5.0 (Robotics Browser)"));
+    /// ```
+    pub fn append_anchored(&mut self, bots: &[&str]) {
+        for bot in bots.iter().filter(|bot| !bot.trim().is_empty()) {
+            self.user_agent_patterns
+                .insert(Anchoring::Boundary.apply(&self.normalize_pattern(bot)));
+        }
+        self.update_regex()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anchoring, Bots, BotsBuilder};
+
+    #[test]
+    fn token_end_anchoring() {
+        let mut bots = Bots::empty();
+        bots.set_default_append_anchoring(Anchoring::TokenEnd);
+        bots.append(&["bot"]);
+        assert!(!bots.is_bot("robot-free zone"));
+        assert!(bots.is_bot("Googlebot"));
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; Googlebot/2.1)"));
+
+        bots.append_raw(&["free"]);
+        assert!(bots.is_bot("robot-free zone"));
+    }
+
+    #[test]
+    fn start_anchoring() {
+        let bots = BotsBuilder::new()
+            .append(&["slurp"])
+            .default_append_anchoring(Anchoring::Start)
+            .append(&["curl|wget"])
+            .build()
+            .unwrap();
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; Yahoo! Slurp)"));
+        assert!(bots.is_bot("Wget/1.21"));
+        assert!(!bots.is_bot("Mozilla/5.0 curl/8.0"));
+
+        let mut bots = bots;
+        bots.append(&["httpie"]);
+        assert!(!bots.is_bot("Mozilla/5.0 HTTPie/3.2"));
+        assert!(bots.is_bot("HTTPie/3.2"));
+    }
+
+    #[test]
+    fn boundary_anchoring() {
+        let robotics = "Mozilla/5.0 (X11; Linux x86_64) RoboticsLab/2.0";
+
+        let mut bots = Bots::empty();
+        bots.append(&["bot"]);
+        assert!(bots.is_bot(robotics));
+
+        let mut bots = Bots::empty();
+        bots.append_anchored(&["bot", "me"]);
+        assert!(!bots.is_bot(robotics));
+        assert!(!bots.is_bot("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Chrome/120.0 Safari/537.36 Edge/120.0 Homepage"));
+        assert!(bots.is_bot("Bot/1.0"));
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; Me/1.0)"));
+        assert!(bots.contains(r"\b(?:bot)\b"));
+
+        bots.remove(&[r"\b(?:bot)\b"]);
+        assert!(!bots.is_bot("Bot/1.0"));
+    }
+}
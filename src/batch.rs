@@ -0,0 +1,137 @@
+use crate::Bots;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+impl Bots {
+    /// Returns the [`Bots::is_bot`] verdict of each user-agent, in the order of the input.
+    ///
+    /// This is the same as calling `is_bot` in a loop, which already reuses a per-thread buffer for
+    /// lowercasing, so it is mostly a convenience for log analysis. The result holds one byte per
+    /// user-agent, while the user-agents are only borrowed one at a time, so a very large input can be
+    /// streamed from a file in chunks to bound memory use. Use [`Bots::dedup_detect`] when the input
+    /// repeats a small number of user-agents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    /// let verdicts = bots.is_bot_batch(["Googlebot/2.1", "Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"]);
+    ///
+    /// assert_eq!(verdicts, vec![true, false]);
+    /// ```
+    pub fn is_bot_batch<'a>(&self, user_agents: impl IntoIterator<Item = &'a str>) -> Vec<bool> {
+        user_agents
+            .into_iter()
+            .map(|user_agent| self.is_bot(user_agent))
+            .collect()
+    }
+
+    /// Returns the [`Bots::is_bot`] verdict of each user-agent in parallel on the rayon thread pool,
+    /// in the order of the input.
+    ///
+    /// The detection hook, see [`Bots::on_detection`], may be called from several threads at once and
+    /// in any order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    /// let verdicts = bots.is_bot_batch_par(&["Googlebot/2.1", "Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"]);
+    ///
+    /// assert_eq!(verdicts, vec![true, false]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn is_bot_batch_par(&self, user_agents: &[&str]) -> Vec<bool> {
+        user_agents
+            .par_iter()
+            .map(|user_agent| self.is_bot(user_agent))
+            .collect()
+    }
+
+    /// Returns the verdict of each distinct user-agent in a batch, matching every distinct user-agent
+    /// exactly once.
+    ///
+    /// Log batches often repeat a small number of user-agents many times, so a batch of a million rows
+    /// with five thousand distinct user-agents only runs five thousand matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    /// let verdicts = bots.dedup_detect(&["Googlebot/2.1", "Mozilla/5.0", "Googlebot/2.1"]);
+    ///
+    /// assert_eq!(verdicts.len(), 2);
+    /// assert!(verdicts["Googlebot/2.1"]);
+    /// assert!(!verdicts["Mozilla/5.0"]);
+    /// ```
+    pub fn dedup_detect<'a>(&self, user_agents: &'a [&'a str]) -> HashMap<&'a str, bool> {
+        let mut verdicts = HashMap::new();
+        for &user_agent in user_agents {
+            verdicts
+                .entry(user_agent)
+                .or_insert_with(|| self.is_bot(user_agent));
+        }
+        verdicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn dedup_detect() {
+        let detections = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&detections);
+        let mut bots = Bots::default();
+        bots.on_detection(move |_, _| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        let googlebot = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+        let browser = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+        let curl = "curl/8.4.0";
+        let user_agents = [googlebot, browser, googlebot, curl, browser, googlebot];
+
+        let verdicts = bots.dedup_detect(&user_agents);
+        assert_eq!(verdicts.len(), 3);
+        assert!(verdicts[googlebot]);
+        assert!(!verdicts[browser]);
+        assert!(verdicts[curl]);
+        assert_eq!(detections.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn is_bot_batch() {
+        let bots = Bots::default();
+        let user_agents = [
+            "Googlebot/2.1 (+http://www.google.com/bot.html)",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36",
+            "",
+            "curl/8.4.0",
+        ];
+        let expected = vec![true, false, false, true];
+        assert_eq!(bots.is_bot_batch(user_agents), expected);
+
+        let owned: Vec<String> = user_agents.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            bots.is_bot_batch(owned.iter().map(String::as_str)),
+            expected
+        );
+        assert!(bots.is_bot_batch(std::iter::empty()).is_empty());
+
+        #[cfg(feature = "rayon")]
+        assert_eq!(bots.is_bot_batch_par(&user_agents), expected);
+    }
+}
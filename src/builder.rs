@@ -0,0 +1,477 @@
+use crate::engine::Regex;
+use crate::{
+    global, limits::SizeLimits, normalize::Normalizer, Anchoring, BotCategory, Bots, BotsError,
+    ResolutionPolicy, BOT_PATTERNS,
+};
+use std::collections::HashSet;
+
+/// What [`BotsBuilder::build`] does when more patterns are supplied than [`BotsBuilder::max_patterns`] allows
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Fail with [`BotsError::TooManyPatterns`]
+    #[default]
+    Error,
+    /// Keep the first patterns, in the order they were added, up to the maximum
+    Truncate,
+}
+
+/// Builder to configure a [`Bots`] instance, compiling the combined regular expression once in [`BotsBuilder::build`]
+///
+/// # Example
+///
+/// ```
+/// use isbot::BotsBuilder;
+///
+/// let bots = BotsBuilder::new()
+///     .with_defaults()
+///     .append(&[r"CustomNewTestB0T\s/\d\.\d"])
+///     .build()
+///     .unwrap();
+///
+/// assert!(bots.is_bot("Googlebot"));
+/// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BotsBuilder {
+    patterns: Vec<String>,
+    max_patterns: Option<usize>,
+    overflow: OverflowPolicy,
+    size_limits: SizeLimits,
+    noise: Vec<String>,
+    allow: Vec<String>,
+    excluded_categories: HashSet<BotCategory>,
+    resolution: ResolutionPolicy,
+    append_anchoring: Anchoring,
+}
+
+impl BotsBuilder {
+    /// Constructs a new builder without any user-agent patterns.
+    pub fn new() -> Self {
+        BotsBuilder::default()
+    }
+
+    /// Adds the default user-agent patterns, the same patterns used by [`Bots::default`].
+    ///
+    /// These are the compiled in patterns, unless they were replaced for the process with
+    /// [`set_global_default_patterns`](crate::set_global_default_patterns).
+    pub fn with_defaults(self) -> Self {
+        match global::default_override() {
+            Some(patterns) => self.patterns(&patterns),
+            None => self.patterns(BOT_PATTERNS),
+        }
+    }
+
+    /// Adds bot user-agent regular expression entries delimited by a newline.
+    ///
+    /// Blank lines are ignored and directives are applied, matching [`Bots::new`].
+    pub fn patterns(mut self, bot_entries: &str) -> Self {
+        self.patterns.extend(
+            bot_entries
+                .to_ascii_lowercase()
+                .lines()
+                .filter_map(Bots::parse_line),
+        );
+        self
+    }
+
+    /// Appends bot user-agent regular expression patterns, with the anchoring set by
+    /// [`BotsBuilder::default_append_anchoring`].
+    ///
+    /// Patterns are trimmed and converted to lowercase like [`Bots::append`], so padded duplicates
+    /// collapse into one pattern.
+    pub fn append(mut self, bots: &[&str]) -> Self {
+        let anchoring = self.append_anchoring;
+        self.patterns.extend(
+            bots.iter()
+                .filter(|bot| !bot.trim().is_empty())
+                .map(|bot| anchoring.apply(&Bots::lowercase_pattern(bot))),
+        );
+        self
+    }
+
+    /// Removes patterns added so far, like [`Bots::remove`]. Patterns are trimmed and compared in
+    /// lowercase.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::BotsBuilder;
+    ///
+    /// let bots = BotsBuilder::new()
+    ///     .with_defaults()
+    ///     .remove(&["Chrome-Lighthouse"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(!bots.contains("chrome-lighthouse"));
+    /// ```
+    pub fn remove(mut self, bots: &[&str]) -> Self {
+        let removed: HashSet<String> = bots
+            .iter()
+            .map(|bot| Bots::lowercase_pattern(bot))
+            .collect();
+        self.patterns.retain(|pattern| !removed.contains(pattern));
+        self
+    }
+
+    /// Sets the anchoring applied to patterns passed to later [`BotsBuilder::append`] calls, and to
+    /// [`Bots::append`] on the built instance.
+    ///
+    /// Patterns added before the anchoring is set, or with [`BotsBuilder::patterns`], are not changed.
+    pub fn default_append_anchoring(mut self, anchoring: Anchoring) -> Self {
+        self.append_anchoring = anchoring;
+        self
+    }
+
+    /// Limits the number of distinct patterns accepted by [`BotsBuilder::build`].
+    ///
+    /// Use this as a guard when loading community or user-supplied pattern lists, so a
+    /// malicious list cannot produce an arbitrarily large regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotsBuilder, BotsError, OverflowPolicy};
+    ///
+    /// let result = BotsBuilder::new().patterns("a\nb\nc").max_patterns(2).build();
+    /// assert!(matches!(result, Err(BotsError::TooManyPatterns { limit: 2, count: 3 })));
+    ///
+    /// let bots = BotsBuilder::new()
+    ///     .patterns("a\nb\nc")
+    ///     .max_patterns(2)
+    ///     .on_overflow(OverflowPolicy::Truncate)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(bots.is_bot("b"));
+    /// assert!(!bots.is_bot("c"));
+    /// ```
+    pub fn max_patterns(mut self, max: usize) -> Self {
+        self.max_patterns = Some(max);
+        self
+    }
+
+    /// Sets what happens when [`BotsBuilder::max_patterns`] is exceeded. Defaults to [`OverflowPolicy::Error`].
+    pub fn on_overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Sets the approximate maximum size, in bytes, of the compiled combined regular expression, like
+    /// [`Bots::set_size_limit`].
+    ///
+    /// Together with [`BotsBuilder::max_patterns`] this bounds both the number of patterns and the
+    /// memory they compile to. [`BotsBuilder::build`] returns [`BotsError::SizeLimitExceeded`] if the
+    /// patterns exceed the limit, and the built instance keeps the limit for later appends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotsBuilder, BotsError};
+    ///
+    /// let result = BotsBuilder::new().with_defaults().size_limit(1024).build();
+    /// assert!(matches!(result, Err(BotsError::SizeLimitExceeded { .. })));
+    ///
+    /// let bots = BotsBuilder::new()
+    ///     .patterns("googlebot")
+    ///     .size_limit(10 * 1024)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    pub fn size_limit(mut self, bytes: usize) -> Self {
+        self.size_limits.size = Some(bytes);
+        self
+    }
+
+    /// Sets the approximate maximum size, in bytes, of the lazy DFA cache used while matching the
+    /// combined regular expression, like [`Bots::set_dfa_size_limit`].
+    pub fn dfa_size_limit(mut self, bytes: usize) -> Self {
+        self.size_limits.dfa_size = Some(bytes);
+        self
+    }
+
+    /// Strips noise from user-agents before matching, such as a fake `Accept-Language` token
+    /// appended by a bot to mimic a browser.
+    ///
+    /// Each regular expression is matched against the lowercase user-agent and every match is
+    /// removed before the bot patterns are checked. Patterns are trimmed like [`BotsBuilder::append`],
+    /// so write a leading or trailing space as `[ ]`. Stripping is disabled by default. Overly broad
+    /// noise patterns can remove the text that makes a browser look like a browser, so keep them
+    /// as specific as possible to avoid false positives.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::BotsBuilder;
+    ///
+    /// let bots = BotsBuilder::new()
+    ///     .patterns(r"^scraperbot/\d+$")
+    ///     .strip_noise(&[r"\s*\(en-us\)"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(bots.is_bot("ScraperBot/2 (en-US)"));
+    /// ```
+    pub fn strip_noise(mut self, patterns: &[&str]) -> Self {
+        self.noise.extend(
+            patterns
+                .iter()
+                .filter(|pattern| !pattern.trim().is_empty())
+                .map(|pattern| Bots::lowercase_pattern(pattern)),
+        );
+        self
+    }
+
+    /// Allows user-agents matching any of the regular expressions, even if they match a bot pattern.
+    ///
+    /// Patterns are trimmed and converted to lowercase like [`Bots::allow`].
+    ///
+    /// Whether the allowlist also overrides categorized patterns depends on [`BotsBuilder::resolution`].
+    pub fn allow(mut self, patterns: &[&str]) -> Self {
+        self.allow.extend(
+            patterns
+                .iter()
+                .filter(|pattern| !pattern.trim().is_empty())
+                .map(|pattern| Bots::lowercase_pattern(pattern)),
+        );
+        self
+    }
+
+    /// Ignores the patterns of a category, so user-agents only matching them are not bots.
+    ///
+    /// The category check, such as [`Bots::is_automation`], also no longer matches.
+    pub fn exclude_category(mut self, category: BotCategory) -> Self {
+        self.excluded_categories.insert(category);
+        self
+    }
+
+    /// Sets the order in which the allowlist and categorized patterns are consulted, defaulting to
+    /// [`ResolutionPolicy::AllowlistFirst`].
+    ///
+    /// The layers are resolved in this order:
+    ///
+    /// 1. Excluded categories never match.
+    /// 2. With [`ResolutionPolicy::CategoriesFirst`], a match of a categorized pattern is a bot.
+    /// 3. A user-agent matching the allowlist is not a bot.
+    /// 4. A user-agent matching any remaining pattern is a bot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotsBuilder, ResolutionPolicy};
+    ///
+    /// let headless = "Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36";
+    /// let builder = BotsBuilder::new().with_defaults().allow(&["linux"]);
+    ///
+    /// assert!(!builder.clone().build().unwrap().is_bot(headless));
+    /// let bots = builder
+    ///     .resolution(ResolutionPolicy::CategoriesFirst)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(bots.is_bot(headless));
+    /// ```
+    pub fn resolution(mut self, policy: ResolutionPolicy) -> Self {
+        self.resolution = policy;
+        self
+    }
+
+    /// Builds the [`Bots`] instance, compiling the combined regular expression once.
+    ///
+    /// Returns [`BotsError::InvalidPattern`] if a pattern is not a valid regular expression, and
+    /// [`BotsError::SizeLimitExceeded`] if the patterns exceed [`BotsBuilder::size_limit`].
+    pub fn build(self) -> Result<Bots, BotsError> {
+        let mut user_agent_patterns = HashSet::new();
+        let mut distinct = Vec::new();
+        for pattern in self.patterns {
+            if user_agent_patterns.insert(pattern.clone()) {
+                distinct.push(pattern);
+            }
+        }
+
+        if let Some(limit) = self.max_patterns {
+            if distinct.len() > limit {
+                match self.overflow {
+                    OverflowPolicy::Error => {
+                        return Err(BotsError::TooManyPatterns {
+                            limit,
+                            count: distinct.len(),
+                        })
+                    }
+                    OverflowPolicy::Truncate => {
+                        for pattern in &distinct[limit..] {
+                            user_agent_patterns.remove(pattern);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut bots = Bots::empty();
+        bots.size_limits = self.size_limits;
+        bots.user_agent_patterns = user_agent_patterns;
+        bots.try_update_regex()?;
+        for pattern in self.noise {
+            let regex = Regex::new(&pattern).map_err(|source| BotsError::InvalidPattern {
+                line: None,
+                pattern,
+                source,
+            })?;
+            bots.normalizers.push(Normalizer::new(regex, ""));
+        }
+        for pattern in self.allow {
+            Regex::new(&pattern).map_err(|source| BotsError::InvalidPattern {
+                line: None,
+                pattern: pattern.clone(),
+                source,
+            })?;
+            bots.layers.allow_patterns.insert(pattern);
+        }
+        bots.layers.update_allow_regex();
+        bots.layers.excluded_categories = self.excluded_categories;
+        bots.layers.policy = self.resolution;
+        bots.append_anchoring = self.append_anchoring;
+        Ok(bots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, BotsBuilder, BotsError, OverflowPolicy};
+
+    #[test]
+    fn max_patterns_exceeded() {
+        let result = BotsBuilder::new()
+            .patterns("one\ntwo\nthree\nthree")
+            .max_patterns(2)
+            .build();
+        assert!(matches!(
+            result,
+            Err(BotsError::TooManyPatterns { limit: 2, count: 3 })
+        ));
+    }
+
+    #[test]
+    fn max_patterns_not_exceeded() {
+        let bots = BotsBuilder::new()
+            .patterns("one\ntwo\nthree\nthree")
+            .max_patterns(3)
+            .build()
+            .unwrap();
+        assert!(bots.is_bot("one"));
+        assert!(bots.is_bot("three"));
+    }
+
+    #[test]
+    fn max_patterns_truncated() {
+        let bots = BotsBuilder::new()
+            .append(&["one", "two", "three"])
+            .max_patterns(2)
+            .on_overflow(OverflowPolicy::Truncate)
+            .build()
+            .unwrap();
+        assert!(bots.is_bot("one"));
+        assert!(bots.is_bot("two"));
+        assert!(!bots.is_bot("three"));
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn max_patterns_with_size_limits() {
+        let result = BotsBuilder::new()
+            .with_defaults()
+            .max_patterns(10_000)
+            .size_limit(1024)
+            .build();
+        assert!(
+            matches!(result, Err(BotsError::SizeLimitExceeded { .. })),
+            "{:?}",
+            result
+        );
+
+        let result = BotsBuilder::new()
+            .with_defaults()
+            .max_patterns(2)
+            .size_limit(1024)
+            .build();
+        assert!(matches!(
+            result,
+            Err(BotsError::TooManyPatterns { limit: 2, .. })
+        ));
+
+        let mut bots = BotsBuilder::new()
+            .patterns("googlebot\n^curl/")
+            .max_patterns(2)
+            .size_limit(10 * 1024)
+            .dfa_size_limit(64 * 1024)
+            .build()
+            .unwrap();
+        assert!(bots.is_bot("Googlebot/2.1"));
+        assert!(bots.is_bot("curl/7.64.1"));
+
+        let defaults = Bots::default();
+        let patterns: Vec<&str> = defaults
+            .user_agent_patterns
+            .iter()
+            .map(String::as_str)
+            .collect();
+        assert!(matches!(
+            bots.try_append(&patterns),
+            Err(BotsError::SizeLimitExceeded { .. })
+        ));
+        assert_eq!(bots.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "include-default-bots")]
+    fn builder_matches_append_and_remove() {
+        let bots = BotsBuilder::new()
+            .with_defaults()
+            .append(&["CustomNewTestB0T", "AnotherTestB0T"])
+            .remove(&["Chrome-Lighthouse", "AnotherTestB0T", "bingpreview/"])
+            .build()
+            .unwrap();
+
+        let mut expected = Bots::default();
+        expected.append(&["CustomNewTestB0T", "AnotherTestB0T"]);
+        expected.remove(&["Chrome-Lighthouse", "AnotherTestB0T", "bingpreview/"]);
+
+        assert_eq!(bots.user_agent_patterns, expected.user_agent_patterns);
+        for user_agent in [
+            "Mozilla/5.0 (CustomNewTestB0T /1.2)",
+            "AnotherTestB0T/1.0",
+            "Mozilla/5.0 (X11; Linux x86_64) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse",
+            "Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/534+ (KHTML, like Gecko) BingPreview/1.0b",
+            "Googlebot/2.1",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0",
+        ] {
+            assert_eq!(bots.is_bot(user_agent), expected.is_bot(user_agent), "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn builder_trims_patterns() {
+        let bots = BotsBuilder::new()
+            .patterns("googlebot")
+            .append(&[" GoogleBot ", "^curl/\n", " bingbot"])
+            .remove(&[" BingBot "])
+            .allow(&[" ^curl/7\\. "])
+            .strip_noise(&[" [ ]\\(en-us\\) "])
+            .build()
+            .unwrap();
+
+        assert_eq!(bots.len(), 2);
+        assert!(bots.is_bot("curl/8.0"));
+        assert!(!bots.is_bot("curl/7.0"));
+        assert!(!bots.is_bot("bingbot/2.0"));
+        assert!(bots.is_bot("Googlebot/2.1 (en-US)"));
+    }
+
+    #[test]
+    fn builder_reports_invalid_pattern() {
+        let result = BotsBuilder::new().patterns("googlebot\n(unclosed").build();
+        assert!(matches!(
+            result,
+            Err(BotsError::InvalidPattern { pattern, .. }) if pattern == "(unclosed"
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+//! C interface for embedding bot detection in non-Rust applications
+//!
+//! An instance is created with [`isbot_new`], which returns an owned pointer. The caller must pass it
+//! to [`isbot_free`] exactly once, and must not use it afterwards. Between the two calls the instance
+//! is read only, so it can be shared between threads.
+//!
+//! ```c
+//! #include <stdbool.h>
+//!
+//! typedef struct Bots Bots;
+//!
+//! Bots *isbot_new(void);
+//! bool isbot_is_bot(const Bots *bots, const char *user_agent);
+//! void isbot_free(Bots *bots);
+//!
+//! Bots *bots = isbot_new();
+//! bool is_bot = isbot_is_bot(bots, "Googlebot/2.1");
+//! isbot_free(bots);
+//! ```
+
+use crate::Bots;
+use std::ffi::{c_char, CStr};
+
+/// Returns a new instance with the default patterns, owned by the caller and released with
+/// [`isbot_free`].
+#[no_mangle]
+pub extern "C" fn isbot_new() -> *mut Bots {
+    Box::into_raw(Box::new(Bots::default()))
+}
+
+/// Returns `true` if the NUL terminated user-agent is a known bot.
+///
+/// Returns `false` if either pointer is null or the user-agent is not valid UTF-8. The user-agent is
+/// only borrowed for the duration of the call.
+///
+/// # Safety
+///
+/// `bots` must be null or a pointer returned by [`isbot_new`] that was not freed, and `user_agent`
+/// must be null or point to a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn isbot_is_bot(bots: *const Bots, user_agent: *const c_char) -> bool {
+    if bots.is_null() || user_agent.is_null() {
+        return false;
+    }
+    match CStr::from_ptr(user_agent).to_str() {
+        Ok(user_agent) => (*bots).is_bot(user_agent),
+        Err(_) => false,
+    }
+}
+
+/// Releases an instance returned by [`isbot_new`]. Does nothing if `bots` is null.
+///
+/// # Safety
+///
+/// `bots` must be null or a pointer returned by [`isbot_new`] that was not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn isbot_free(bots: *mut Bots) {
+    if !bots.is_null() {
+        drop(Box::from_raw(bots));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{isbot_free, isbot_is_bot, isbot_new};
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn ffi_boundary() {
+        let bots = isbot_new();
+        let bot = CString::new("Googlebot/2.1 (+http://www.google.com/bot.html)").unwrap();
+        let browser = CString::new("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1").unwrap();
+        let invalid = CString::new(b"Googlebot/\xff".to_vec()).unwrap();
+        unsafe {
+            assert!(isbot_is_bot(bots, bot.as_ptr()));
+            assert!(!isbot_is_bot(bots, browser.as_ptr()));
+            assert!(!isbot_is_bot(bots, invalid.as_ptr()));
+            assert!(!isbot_is_bot(bots, ptr::null()));
+            assert!(!isbot_is_bot(ptr::null(), bot.as_ptr()));
+            isbot_free(bots);
+            isbot_free(ptr::null_mut());
+        }
+    }
+}
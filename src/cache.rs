@@ -0,0 +1,134 @@
+use crate::Bots;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard};
+
+/// Wrapper memoizing [`Bots::is_bot`] verdicts for recently seen user-agents
+///
+/// Real traffic repeats a small number of user-agents, so most calls can skip matching. Verdicts are
+/// kept for the `capacity` most recently used user-agents, keyed by the exact user-agent string, and
+/// the least recently used verdict is evicted when the cache is full. The cache is behind a
+/// [`Mutex`], so a `CachedBots` can be shared between threads, but each call takes the lock.
+///
+/// # Example
+///
+/// ```
+/// use isbot::{Bots, CachedBots};
+///
+/// let bots = CachedBots::new(Bots::default(), 1000);
+///
+/// assert!(bots.is_bot("Googlebot/2.1"));
+/// assert!(bots.is_bot("Googlebot/2.1"));
+/// assert_eq!(bots.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct CachedBots {
+    bots: Bots,
+    cache: Option<Mutex<LruCache<String, bool>>>,
+}
+
+impl CachedBots {
+    /// Constructs a new wrapper caching up to `capacity` verdicts. A capacity of `0` disables caching.
+    pub fn new(bots: Bots, capacity: usize) -> Self {
+        CachedBots {
+            bots,
+            cache: NonZeroUsize::new(capacity).map(|capacity| Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Returns `true` the user-agent is a known bot, like [`Bots::is_bot`], using the cached verdict
+    /// if the user-agent was seen recently.
+    ///
+    /// The detection hook, see [`Bots::on_detection`], is called for every bot, including cached ones.
+    pub fn is_bot(&self, user_agent: &str) -> bool {
+        let Some(cache) = &self.cache else {
+            return self.bots.is_bot(user_agent);
+        };
+        let cached = lock(cache).get(user_agent).copied();
+        match cached {
+            Some(is_bot) => {
+                if is_bot {
+                    self.bots.notify_detection(user_agent);
+                }
+                is_bot
+            }
+            None => {
+                // Matched without the lock held, so other threads are not blocked by a slow match
+                let is_bot = self.bots.is_bot(user_agent);
+                lock(cache).put(user_agent.to_string(), is_bot);
+                is_bot
+            }
+        }
+    }
+
+    /// Returns the number of cached verdicts.
+    pub fn len(&self) -> usize {
+        self.cache.as_ref().map_or(0, |cache| lock(cache).len())
+    }
+
+    /// Returns `true` if no verdicts are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached verdicts.
+    pub fn clear(&self) {
+        if let Some(cache) = &self.cache {
+            lock(cache).clear();
+        }
+    }
+
+    /// Returns the wrapped [`Bots`].
+    pub fn bots(&self) -> &Bots {
+        &self.bots
+    }
+
+    /// Returns the wrapped [`Bots`], dropping the cache, such as to change its patterns.
+    pub fn into_inner(self) -> Bots {
+        self.bots
+    }
+}
+
+/// Locks the cache, recovering it if another thread panicked while holding the lock, since a verdict
+/// is either fully inserted or not at all.
+fn lock(cache: &Mutex<LruCache<String, bool>>) -> MutexGuard<'_, LruCache<String, bool>> {
+    cache.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, CachedBots};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn caches_recent_verdicts() {
+        let detected = Arc::new(Mutex::new(Vec::new()));
+        let mut bots = Bots::new("googlebot\n^curl/");
+        let sink = Arc::clone(&detected);
+        bots.on_detection(move |user_agent, _| sink.lock().unwrap().push(user_agent.to_string()));
+
+        let cached = CachedBots::new(bots, 2);
+        assert!(cached.is_empty());
+        assert!(cached.is_bot("Googlebot/2.1"));
+        assert!(cached.is_bot("Googlebot/2.1"));
+        assert!(!cached.is_bot("Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0"));
+        assert_eq!(cached.len(), 2);
+        assert_eq!(detected.lock().unwrap().len(), 2);
+
+        assert!(cached.is_bot("curl/7.64.1"));
+        assert_eq!(cached.len(), 2);
+        assert!(cached.is_bot("Googlebot/2.1"));
+
+        cached.clear();
+        assert!(cached.is_empty());
+        assert!(cached.into_inner().is_bot("curl/7.64.1"));
+    }
+
+    #[test]
+    fn zero_capacity_disables_cache() {
+        let cached = CachedBots::new(Bots::new("googlebot"), 0);
+        assert!(cached.is_bot("Googlebot/2.1"));
+        assert!(!cached.is_bot("Mozilla/5.0"));
+        assert!(cached.is_empty());
+    }
+}
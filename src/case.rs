@@ -0,0 +1,223 @@
+use crate::engine::Regex;
+use crate::matching::PatternSet;
+use crate::{Bots, BotsError};
+use std::collections::HashSet;
+
+/// Patterns matched against the user-agent as given, without lowercasing
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CaseSensitivePatterns {
+    patterns: HashSet<String>,
+    regex: Option<Regex>,
+    set: Option<PatternSet>,
+}
+
+impl CaseSensitivePatterns {
+    pub(crate) fn is_match(&self, user_agent: &str) -> bool {
+        self.regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(user_agent))
+    }
+
+    /// Returns the patterns matching the user-agent as given, in sorted pattern order.
+    pub(crate) fn matches(&self, user_agent: &str) -> Vec<&str> {
+        self.set
+            .as_ref()
+            .map_or_else(Vec::new, |set| set.matches(user_agent))
+    }
+}
+
+impl Bots {
+    /// Constructs a new instance like [`Bots::new`] that keeps the casing of patterns and user-agents.
+    ///
+    /// Patterns are stored as given and matched against the user-agent as given, so `ABC` and `abc`
+    /// are different bots. The mode is kept for the life of the instance: [`Bots::append`],
+    /// [`Bots::remove`], and the other methods taking a pattern do not convert it to lowercase. Use
+    /// `(?i)` in a pattern to match it regardless of casing.
+    ///
+    /// Only the patterns of the instance keep their casing. The built-in lists, such as the device
+    /// hints of [`Bots::device_hint`], the browser tokens, and the categories of default patterns,
+    /// are lowercase and are always matched regardless of casing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new_case_sensitive("^ABC/");
+    /// assert!(bots.is_bot("ABC/1.0"));
+    /// assert!(!bots.is_bot("abc/1.0"));
+    ///
+    /// bots.append(&["(?i)googlebot"]);
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is not a valid regular expression. Use [`Bots::try_new_case_sensitive`]
+    /// for patterns that are not known to be valid.
+    pub fn new_case_sensitive(bot_entries: &str) -> Self {
+        Bots::try_new_case_sensitive(bot_entries).expect("Invalid bot pattern")
+    }
+
+    /// Constructs a new instance like [`Bots::new_case_sensitive`], returning an error instead of
+    /// panicking if an entry is not a valid regular expression.
+    pub fn try_new_case_sensitive(bot_entries: &str) -> Result<Self, BotsError> {
+        let mut bots = Bots::try_from_patterns(Bots::parse_lines(bot_entries))
+            .map_err(|error| Bots::validate_lines(bot_entries).err().unwrap_or(error))?;
+        bots.preserve_case = true;
+        bots.category_regexes = bots.category_regexes_for(&bots.compiled_patterns());
+        Ok(bots)
+    }
+
+    /// Converts a pattern to the form patterns are stored in: trimmed, and lowercase unless the
+    /// instance is case-sensitive.
+    pub(crate) fn normalize_pattern(&self, pattern: &str) -> String {
+        if self.preserve_case {
+            pattern.trim().to_string()
+        } else {
+            Bots::lowercase_pattern(pattern)
+        }
+    }
+
+    /// Converts a pattern to the form stored by an instance that is not case-sensitive: trimmed and
+    /// lowercase. Used where there is no instance to normalize for, such as in the builder.
+    pub(crate) fn lowercase_pattern(pattern: &str) -> String {
+        pattern.trim().to_ascii_lowercase()
+    }
+
+    /// Appends bot user-agent regular expressions that only match the exact casing, such as protocol
+    /// tokens where casing is significant.
+    ///
+    /// Other patterns are matched against the lowercase user-agent, so they stay case-insensitive.
+    /// Case-sensitive patterns are matched against the user-agent as given, before normalizers are
+    /// applied, and are only used by [`Bots::is_bot`] and the methods built on it. Patterns are
+    /// trimmed, blank patterns are skipped, and duplicates are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("googlebot");
+    /// bots.append_case_sensitive(&["^HTTPClient/"]);
+    ///
+    /// assert!(bots.is_bot("HTTPClient/1.0"));
+    /// assert!(!bots.is_bot("HttpClient/1.0"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is not a valid regular expression. Use [`Bots::try_append_case_sensitive`]
+    /// for patterns that are not known to be valid.
+    pub fn append_case_sensitive(&mut self, patterns: &[&str]) {
+        self.try_append_case_sensitive(patterns)
+            .expect("Invalid bot pattern")
+    }
+
+    /// Appends case-sensitive bot user-agent regular expressions like [`Bots::append_case_sensitive`],
+    /// returning an error instead of panicking if a pattern is not a valid regular expression.
+    ///
+    /// Each pattern is compiled on its own before any is added, so on error the patterns are left
+    /// unchanged.
+    pub fn try_append_case_sensitive(&mut self, patterns: &[&str]) -> Result<(), BotsError> {
+        let patterns: Vec<String> = patterns
+            .iter()
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect();
+        Bots::validate_patterns(&patterns)?;
+
+        let case_sensitive = &mut self.case_sensitive;
+        case_sensitive.patterns.extend(patterns);
+        if case_sensitive.patterns.is_empty() {
+            case_sensitive.regex = None;
+            case_sensitive.set = None;
+        } else {
+            case_sensitive.regex = Some(Bots::to_regex(&case_sensitive.patterns));
+            case_sensitive.set = Some(PatternSet::new(&case_sensitive.patterns));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn case_sensitive_patterns() {
+        let mut bots = Bots::new("googlebot");
+        bots.append_case_sensitive(&["TLS/1\\.3", "^HTTPClient/"]);
+
+        assert!(bots.is_bot("Mozilla/5.0 TLS/1.3"));
+        assert!(!bots.is_bot("Mozilla/5.0 tls/1.3"));
+        assert!(bots.is_bot("HTTPClient/2.0"));
+        assert!(!bots.is_bot("httpclient/2.0"));
+
+        assert!(bots.is_bot("GOOGLEBOT/2.1"));
+        assert!(bots.is_bot("googlebot/2.1"));
+    }
+
+    #[test]
+    fn case_sensitive_instance() {
+        let user_agent = "Mozilla/5.0 (compatible; ABC-Agent/2.0)";
+        let lowercase_user_agent = "Mozilla/5.0 (compatible; abc-agent/2.0)";
+
+        let bots = Bots::new("ABC-Agent/");
+        assert!(bots.is_bot(user_agent));
+        assert!(bots.is_bot(lowercase_user_agent));
+
+        let mut bots = Bots::new_case_sensitive("ABC-Agent/");
+        assert!(bots.is_bot(user_agent));
+        assert!(!bots.is_bot(lowercase_user_agent));
+        assert!(bots.contains("ABC-Agent/"));
+        assert!(!bots.contains("abc-agent/"));
+
+        bots.append(&["^XYZ/"]);
+        assert!(bots.is_bot("XYZ/1.0"));
+        assert!(!bots.is_bot("xyz/1.0"));
+
+        bots.remove(&["abc-agent/"]);
+        assert!(bots.is_bot(user_agent));
+        bots.remove(&["ABC-Agent/"]);
+        assert!(!bots.is_bot(user_agent));
+
+        let long_user_agent = format!("XYZ/1.0 {}", "Extension/1.0 ".repeat(30));
+        assert!(bots.is_bot(&long_user_agent));
+        assert!(!bots.is_bot(&long_user_agent.to_ascii_lowercase()));
+    }
+
+    #[test]
+    fn try_append_case_sensitive() {
+        let mut bots = Bots::new("googlebot");
+        assert!(bots
+            .try_append_case_sensitive(&["^HTTPClient/", "(unclosed"])
+            .is_err());
+        assert!(!bots.is_bot("HTTPClient/1.0"));
+
+        bots.try_append_case_sensitive(&[" ^HTTPClient/ "]).unwrap();
+        assert!(bots.is_bot("HTTPClient/1.0"));
+        assert!(!bots.is_bot("httpclient/1.0"));
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn case_sensitive_instance_matches_builtin_lists() {
+        use crate::DeviceType;
+
+        let googlebot = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        let chrome = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        let headless = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36";
+
+        let mut bots = Bots::new_case_sensitive("(?i)googlebot\n^ABC/");
+        assert!(bots.is_bot(googlebot));
+        assert_eq!(bots.device_hint(googlebot), Some(DeviceType::Desktop));
+        assert!(!bots.is_unknown(chrome));
+        assert_eq!(bots.bot_score(chrome), 0.0);
+
+        bots.append(&["headlesschrome"]);
+        assert!(bots.is_automation(headless));
+        assert!(!bots.is_bot(headless));
+        assert!(!bots.is_bot("abc/1.0"));
+    }
+}
@@ -0,0 +1,463 @@
+use crate::Bots;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+/// Load the default pattern categories from a local file, unless the default bots feature is disabled
+#[cfg(feature = "include-default-bots")]
+const BOT_CATEGORIES: &str = include_str!("bot_categories.txt");
+
+/// Do not load any default pattern categories into the compiled library if feature is not enabled
+#[cfg(not(feature = "include-default-bots"))]
+const BOT_CATEGORIES: &str = "";
+
+/// Category of a known bot user-agent pattern
+///
+/// Default patterns are grouped by category in [bot_categories.txt](https://github.com/BryanMorgan/isbot/blob/main/src/bot_categories.txt),
+/// where each `[name]` section header labels the patterns that follow it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum BotCategory {
+    /// Web preservation crawlers such as the Internet Archive
+    Archiver,
+    /// Browser automation frameworks and headless browsers such as Selenium, Playwright, or `HeadlessChrome`
+    Automation,
+    /// E-commerce price scrapers and competitive pricing crawlers such as `PriceGrabber` or `Price2Spy`
+    PriceScraper,
+    /// Search engine crawlers such as Yahoo! Slurp, Seznam, or Sogou
+    SearchEngine,
+    /// Uptime and performance monitoring services such as Pingdom, Datadog, or Zabbix
+    Monitoring,
+    /// General purpose scrapers and site downloaders such as Scrapy or HTTrack
+    Scraper,
+    /// Link preview fetchers of social networks and messengers such as `facebookexternalhit` or WhatsApp
+    SocialPreview,
+    /// A bot matched only by patterns without a category, see [`Bots::classify`]. No pattern is
+    /// assigned this category.
+    Unknown,
+}
+
+impl BotCategory {
+    /// All categories, in declaration order
+    pub const ALL: &'static [BotCategory] = &[
+        BotCategory::Archiver,
+        BotCategory::Automation,
+        BotCategory::PriceScraper,
+        BotCategory::SearchEngine,
+        BotCategory::Monitoring,
+        BotCategory::Scraper,
+        BotCategory::SocialPreview,
+        BotCategory::Unknown,
+    ];
+
+    /// Returns the section name used for the category in `bot_categories.txt`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BotCategory::Archiver => "archiver",
+            BotCategory::Automation => "automation",
+            BotCategory::PriceScraper => "price_scraper",
+            BotCategory::SearchEngine => "search_engine",
+            BotCategory::Monitoring => "monitoring",
+            BotCategory::Scraper => "scraper",
+            BotCategory::SocialPreview => "social_preview",
+            BotCategory::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for BotCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BotCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BotCategory::ALL
+            .iter()
+            .find(|category| category.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| format!("Unknown bot category: {}", s))
+    }
+}
+
+impl Bots {
+    /// Returns the category of a pattern, if the pattern is categorized.
+    ///
+    /// A category set with [`Bots::append_with_category`] takes precedence over the default category.
+    pub(crate) fn pattern_category(&self, pattern: &str) -> Option<BotCategory> {
+        match self.custom_categories.get(pattern) {
+            Some(BotCategory::Unknown) => None,
+            Some(&category) => Some(category),
+            None => default_category(pattern),
+        }
+    }
+
+    /// Appends a bot user-agent regular expression in `category`, so [`Bots::classify`] and the
+    /// category checks such as [`Bots::is_automation`] report it.
+    ///
+    /// The pattern is normalized like [`Bots::append`]. Patterns appended without a category are
+    /// classified as [`BotCategory::Unknown`], and appending a pattern again replaces its category,
+    /// including the category of a default pattern. [`BotCategory::Unknown`] leaves the pattern
+    /// without a category.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots};
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.append_with_category("^acmepricewatch/", BotCategory::PriceScraper);
+    ///
+    /// assert_eq!(bots.classify("AcmePriceWatch/2.0"), Some(BotCategory::PriceScraper));
+    /// assert_eq!(bots.which_bot("AcmePriceWatch/2.0"), Some("^acmepricewatch/"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression.
+    pub fn append_with_category(&mut self, pattern: &str, category: BotCategory) {
+        if pattern.trim().is_empty() {
+            return;
+        }
+        let pattern = self
+            .append_anchoring
+            .apply(&self.normalize_pattern(pattern));
+        self.custom_categories.insert(pattern.clone(), category);
+        self.user_agent_patterns.insert(pattern);
+        self.update_regex()
+    }
+
+    /// Appends the patterns of `other` in `category`, such as to build a detector from a single category
+    /// of the default patterns.
+    ///
+    /// Patterns in other categories or without a category are not copied, and disabled patterns of
+    /// `other` are ignored. Patterns categorized with [`Bots::append_with_category`] keep their category.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots};
+    ///
+    /// let mut bots = Bots::new("customnewtestb0t");
+    /// bots.append_category_from(&Bots::default(), BotCategory::Automation);
+    ///
+    /// assert!(bots.is_bot("Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36"));
+    /// assert!(!bots.is_bot("Mozilla/5.0 (compatible; archive.org_bot +http://archive.org/details/archive.org_bot)"));
+    /// ```
+    pub fn append_category_from(&mut self, other: &Bots, category: BotCategory) {
+        let patterns: Vec<String> = other
+            .user_agent_patterns
+            .iter()
+            .filter(|pattern| other.pattern_category(pattern) == Some(category))
+            .cloned()
+            .collect();
+        for pattern in patterns {
+            if other.custom_categories.contains_key(&pattern) {
+                self.custom_categories.insert(pattern.clone(), category);
+            }
+            self.user_agent_patterns.insert(pattern);
+        }
+        self.update_regex();
+    }
+
+    /// Returns the categorized default patterns grouped by category, such as for documenting the bots
+    /// that are detected.
+    ///
+    /// Patterns within each category are sorted. Default patterns without a category and patterns added
+    /// to an instance are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots};
+    ///
+    /// let grouped = Bots::patterns_grouped();
+    /// assert!(grouped[&BotCategory::Automation].contains(&"selenium"));
+    /// ```
+    pub fn patterns_grouped() -> BTreeMap<BotCategory, Vec<&'static str>> {
+        let mut grouped: BTreeMap<BotCategory, Vec<&'static str>> = BTreeMap::new();
+        for (&pattern, &category) in default_categories() {
+            grouped.entry(category).or_default().push(pattern);
+        }
+        for patterns in grouped.values_mut() {
+            patterns.sort_unstable();
+        }
+        grouped
+    }
+
+    /// Returns the category of a bot user-agent, such as [`BotCategory::SearchEngine`] for analytics,
+    /// or `None` if it is not a bot.
+    ///
+    /// Categories are checked in [`BotCategory::ALL`] order and the first match is returned. Bots that
+    /// only match patterns without a category, including patterns added to an instance without
+    /// [`Bots::append_with_category`], are [`BotCategory::Unknown`]. The detection hook is not called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots};
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.classify("Mozilla/5.0 (compatible; Yahoo! Slurp; http://help.yahoo.com/help/us/ysearch/slurp)"), Some(BotCategory::SearchEngine));
+    /// assert_eq!(bots.classify("Datadog Agent/7.45.0"), Some(BotCategory::Monitoring));
+    /// assert_eq!(bots.classify("curl/7.64.1"), Some(BotCategory::Unknown));
+    /// assert_eq!(bots.classify("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0"), None);
+    /// ```
+    pub fn classify(&self, user_agent: &str) -> Option<BotCategory> {
+        if !self.detect(user_agent) {
+            return None;
+        }
+        BotCategory::ALL
+            .iter()
+            .copied()
+            .find(|&category| self.is_category(user_agent, category))
+            .or(Some(BotCategory::Unknown))
+    }
+
+    /// Pairs each user-agent with its bot category as returned by [`Bots::classify`], or `None` if it
+    /// is not a bot.
+    ///
+    /// Bots that are not in a category are [`BotCategory::Unknown`]. User-agents are categorized
+    /// lazily as the iterator advances, so a large corpus can be streamed to a writer without
+    /// collecting the results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots};
+    ///
+    /// let bots = Bots::default();
+    /// let user_agents = [
+    ///     "Mozilla/5.0 (compatible; archive.org_bot +http://archive.org/details/archive.org_bot)",
+    ///     "Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36",
+    ///     "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0",
+    ///     "curl/7.64.1",
+    /// ];
+    /// let categories: Vec<_> = bots.categorize_corpus(&user_agents).map(|(_, c)| c).collect();
+    /// assert_eq!(
+    ///     categories,
+    ///     [
+    ///         Some(BotCategory::Archiver),
+    ///         Some(BotCategory::Automation),
+    ///         None,
+    ///         Some(BotCategory::Unknown),
+    ///     ]
+    /// );
+    /// ```
+    pub fn categorize_corpus<'a>(
+        &'a self,
+        user_agents: &'a [&'a str],
+    ) -> impl Iterator<Item = (&'a str, Option<BotCategory>)> + 'a {
+        user_agents
+            .iter()
+            .map(move |&user_agent| (user_agent, self.classify(user_agent)))
+    }
+}
+
+/// Returns the category of a default pattern, if the pattern is categorized.
+pub(crate) fn default_category(pattern: &str) -> Option<BotCategory> {
+    default_categories().get(pattern).copied()
+}
+
+fn default_categories() -> &'static HashMap<&'static str, BotCategory> {
+    static DEFAULT_CATEGORIES: OnceLock<HashMap<&'static str, BotCategory>> = OnceLock::new();
+    DEFAULT_CATEGORIES.get_or_init(|| parse_categories(BOT_CATEGORIES))
+}
+
+fn parse_categories(entries: &str) -> HashMap<&str, BotCategory> {
+    let mut categories = HashMap::new();
+    let mut current = None;
+    for line in entries.lines().filter(|l| !l.trim().is_empty()) {
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = Some(section.parse().expect("Invalid bot category section"));
+        } else if let Some(category) = current {
+            categories.insert(line, category);
+        }
+    }
+    categories
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use super::{parse_categories, BOT_CATEGORIES};
+    use crate::{BotCategory, Bots};
+
+    #[test]
+    fn categorized_patterns_are_default_patterns() {
+        let bots = Bots::default();
+        for pattern in parse_categories(BOT_CATEGORIES).keys() {
+            assert!(
+                bots.user_agent_patterns.contains(*pattern),
+                "Categorized pattern is not a default pattern: {}",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn classify() {
+        let bots = Bots::default();
+        let classified = [
+            (
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+                Some(BotCategory::SearchEngine),
+            ),
+            (
+                "Pingdom.com_bot_version_1.4_(http://www.pingdom.com/)",
+                Some(BotCategory::Monitoring),
+            ),
+            (
+                "Scrapy/2.11.0 (+https://scrapy.org)",
+                Some(BotCategory::Scraper),
+            ),
+            (
+                "facebookexternalhit/1.1 (+http://www.facebook.com/externalhit_uatext.php)",
+                Some(BotCategory::SocialPreview),
+            ),
+            (
+                "Mozilla/5.0 (compatible; heritrix/3.4.0 +http://example.org)",
+                Some(BotCategory::Archiver),
+            ),
+            ("python-requests/2.31.0", Some(BotCategory::Unknown)),
+            (
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0",
+                None,
+            ),
+        ];
+        for (user_agent, category) in classified {
+            assert_eq!(bots.classify(user_agent), category, "{}", user_agent);
+        }
+        assert!(!Bots::patterns_grouped().contains_key(&BotCategory::Unknown));
+
+        let bots = Bots::builder()
+            .with_defaults()
+            .exclude_category(BotCategory::SearchEngine)
+            .build()
+            .unwrap();
+        assert_eq!(bots.classify("Googlebot/2.1"), Some(BotCategory::Unknown));
+    }
+
+    #[test]
+    fn categorize_corpus() {
+        let bots = Bots::default();
+        let user_agents = [
+            "Mozilla/5.0 (compatible; heritrix/3.4.0 +http://example.org)",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0",
+            "Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36",
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+            "ArchiveTeam ArchiveBot/20230101",
+            "python-requests/2.31.0",
+        ];
+        let categorized: Vec<_> = bots.categorize_corpus(&user_agents).collect();
+        assert_eq!(
+            categorized,
+            [
+                (user_agents[0], Some(BotCategory::Archiver)),
+                (user_agents[1], None),
+                (user_agents[2], Some(BotCategory::Automation)),
+                (user_agents[3], Some(BotCategory::SearchEngine)),
+                (user_agents[4], Some(BotCategory::Archiver)),
+                (user_agents[5], Some(BotCategory::Unknown)),
+            ]
+        );
+
+        let bots = Bots::new("customnewtestb0t");
+        let user_agents = ["CustomNewTestB0T/1.0", "Mozilla/5.0"];
+        for (user_agent, category) in bots.categorize_corpus(&user_agents) {
+            assert_eq!(category, bots.classify(user_agent), "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn patterns_grouped() {
+        let grouped = Bots::patterns_grouped();
+        for category in BotCategory::ALL {
+            if *category == BotCategory::Unknown {
+                continue;
+            }
+            assert!(
+                !grouped[category].is_empty(),
+                "Empty category: {}",
+                category
+            );
+        }
+
+        let bots = Bots::default();
+        let categorized = bots
+            .user_agent_patterns
+            .iter()
+            .filter(|pattern| bots.pattern_category(pattern).is_some())
+            .count();
+        assert_eq!(grouped.values().map(Vec::len).sum::<usize>(), categorized);
+    }
+
+    #[test]
+    fn append_category_from() {
+        let defaults = Bots::default();
+        let mut bots = Bots::new("customnewtestb0t");
+        bots.append_category_from(&defaults, BotCategory::Archiver);
+
+        let grouped = Bots::patterns_grouped();
+        for pattern in &grouped[&BotCategory::Archiver] {
+            assert!(bots.user_agent_patterns.contains(*pattern));
+        }
+        for pattern in &grouped[&BotCategory::Automation] {
+            assert!(!bots.user_agent_patterns.contains(*pattern));
+        }
+        assert_eq!(
+            bots.user_agent_patterns.len(),
+            grouped[&BotCategory::Archiver].len() + 1
+        );
+        assert!(bots.is_archiver("Mozilla/5.0 (compatible; heritrix/3.4.0 +http://example.org)"));
+        assert!(
+            !bots.is_bot("Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36")
+        );
+        assert!(!bots.is_bot("Googlebot/2.1"));
+    }
+
+    #[test]
+    fn append_category_from_custom_categories() {
+        let mut source = Bots::new("googlebot");
+        source.append_with_category("^acmepricewatch/", BotCategory::PriceScraper);
+        source.append_with_category("^acmefeeds/", BotCategory::SocialPreview);
+
+        let mut bots = Bots::empty();
+        bots.append_category_from(&source, BotCategory::PriceScraper);
+
+        assert_eq!(bots.len(), 1);
+        assert_eq!(
+            bots.classify("AcmePriceWatch/2.0"),
+            Some(BotCategory::PriceScraper)
+        );
+        assert!(!bots.is_bot("AcmeFeeds/1.0"));
+    }
+
+    #[test]
+    fn append_with_category() {
+        let mut bots = Bots::default();
+        let user_agent = "ShopLens/3.1";
+        assert_eq!(bots.classify(user_agent), None);
+
+        bots.append_with_category("ShopLens/", BotCategory::Scraper);
+        assert_eq!(bots.classify(user_agent), Some(BotCategory::Scraper));
+        assert_eq!(bots.which_bot(user_agent), Some("shoplens/"));
+
+        assert_eq!(bots.classify("RetailPulse/1.0"), None);
+        bots.append(&["^retailpulse/"]);
+        assert_eq!(bots.classify("RetailPulse/1.0"), Some(BotCategory::Unknown));
+
+        bots.append_with_category("googlebot", BotCategory::Unknown);
+        assert_eq!(bots.classify("Googlebot/2.1"), Some(BotCategory::Unknown));
+
+        bots.clear();
+        bots.append(&["shoplens/"]);
+        assert_eq!(bots.classify(user_agent), Some(BotCategory::Unknown));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Bots, BotsError};
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, fs, path::Path};
+
+impl Bots {
+    /// Appends the bot user-agent regular expressions in a file, only if the file has the expected
+    /// SHA-256 checksum.
+    ///
+    /// The file uses the same format as [`Bots::new`]. The checksum is compared as hexadecimal,
+    /// ignoring case, before any pattern is parsed, so a tampered or truncated download, such as one
+    /// adding a catch-all pattern, is refused and the instance is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.append_from_file_verified(
+    ///     "bot_patterns.txt",
+    ///     "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    /// )
+    /// .expect("Bot patterns failed verification");
+    /// ```
+    pub fn append_from_file_verified(
+        &mut self,
+        path: impl AsRef<Path>,
+        expected_sha256: &str,
+    ) -> Result<(), BotsError> {
+        let contents = fs::read(path).map_err(BotsError::Io)?;
+        let actual = Sha256::digest(&contents)
+            .iter()
+            .fold(String::new(), |mut hex, byte| {
+                let _ = write!(hex, "{:02x}", byte);
+                hex
+            });
+        if !actual.eq_ignore_ascii_case(expected_sha256.trim()) {
+            return Err(BotsError::ChecksumMismatch {
+                expected: expected_sha256.trim().to_ascii_lowercase(),
+                actual,
+            });
+        }
+
+        let contents = String::from_utf8_lossy(&contents).to_ascii_lowercase();
+        Bots::validate_lines(&contents)?;
+        self.user_agent_patterns
+            .extend(contents.lines().filter_map(Bots::parse_line));
+        self.update_regex();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, BotsError};
+    use std::fs;
+
+    /// SHA-256 checksum of `customnewtestb0t\n^fancyb0t/\n`
+    const CHECKSUM: &str = "94d7fa60e7d72df1b55ec0ee719d62bb060023717dfc99049f38e7f6a0c16c3f";
+
+    #[test]
+    fn verified_file() {
+        let path = std::env::temp_dir().join(format!("isbot-verified-{}.txt", std::process::id()));
+        fs::write(&path, "customnewtestb0t\n^fancyb0t/\n").unwrap();
+
+        let mut bots = Bots::empty();
+        let result = bots.append_from_file_verified(&path, &"0".repeat(64));
+        assert!(
+            matches!(result, Err(BotsError::ChecksumMismatch { ref actual, .. }) if actual == CHECKSUM)
+        );
+        assert!(bots.user_agent_patterns.is_empty());
+
+        bots.append_from_file_verified(&path, &CHECKSUM.to_ascii_uppercase())
+            .unwrap();
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T)"));
+        assert!(bots.is_bot("FancyB0T/1.0"));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            bots.append_from_file_verified(&path, CHECKSUM),
+            Err(BotsError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::engine::Regex;
+use crate::Bots;
+use std::sync::OnceLock;
+
+/// Load the default device hints from a local file, unless the default bots feature is disabled
+#[cfg(feature = "include-default-bots")]
+const BOT_DEVICES: &str = include_str!("bot_devices.txt");
+
+/// Do not load any default device hints into the compiled library if feature is not enabled
+#[cfg(not(feature = "include-default-bots"))]
+const BOT_DEVICES: &str = "";
+
+/// Device a bot declares in its user-agent, see [`Bots::device_hint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceType {
+    /// Mobile crawlers such as `AdsBot-Google-Mobile` or the Googlebot smartphone crawler
+    Mobile,
+    /// Desktop crawlers such as the Googlebot desktop crawler
+    Desktop,
+    /// The bot does not declare a recognized device
+    Unknown,
+}
+
+struct DeviceHints {
+    mobile: Regex,
+    desktop: Regex,
+}
+
+impl Bots {
+    /// Returns the device declared by a known bot, or `None` if the user-agent is not a bot.
+    ///
+    /// Devices are detected with the hints in [bot_devices.txt](https://github.com/BryanMorgan/isbot/blob/main/src/bot_devices.txt).
+    /// Mobile hints are checked before desktop hints, since mobile crawlers often also mention a desktop
+    /// crawler token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Bots, DeviceType};
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.device_hint("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"), Some(DeviceType::Desktop));
+    /// assert_eq!(bots.device_hint("AdsBot-Google-Mobile (+http://www.google.com/mobile/adsbot.html)"), Some(DeviceType::Mobile));
+    /// assert_eq!(bots.device_hint("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"), None);
+    /// ```
+    pub fn device_hint(&self, user_agent: &str) -> Option<DeviceType> {
+        if !self.is_bot(user_agent) {
+            return None;
+        }
+        let hints = default_hints();
+        let normalized = self.normalize_user_agent(user_agent);
+        let user_agent = self.lowercase_normalized(&normalized);
+        Some(if hints.mobile.is_match(&user_agent) {
+            DeviceType::Mobile
+        } else if hints.desktop.is_match(&user_agent) {
+            DeviceType::Desktop
+        } else {
+            DeviceType::Unknown
+        })
+    }
+}
+
+fn default_hints() -> &'static DeviceHints {
+    static DEFAULT_HINTS: OnceLock<DeviceHints> = OnceLock::new();
+    DEFAULT_HINTS.get_or_init(|| parse_hints(BOT_DEVICES))
+}
+
+fn parse_hints(entries: &str) -> DeviceHints {
+    let mut mobile = Vec::new();
+    let mut desktop = Vec::new();
+    let mut current = None;
+    for line in entries.lines().filter(|l| !l.trim().is_empty()) {
+        match line {
+            "[mobile]" => current = Some(&mut mobile),
+            "[desktop]" => current = Some(&mut desktop),
+            _ => current
+                .as_mut()
+                .expect("Device hint outside of a section")
+                .push(line),
+        }
+    }
+    DeviceHints {
+        mobile: to_regex(&mobile),
+        desktop: to_regex(&desktop),
+    }
+}
+
+fn to_regex(hints: &[&str]) -> Regex {
+    let pattern = if hints.is_empty() {
+        r"[^\s\S]".to_string()
+    } else {
+        hints.join("|")
+    };
+    Regex::new(&pattern).expect("Invalid device hint")
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::{Bots, DeviceType};
+
+    #[test]
+    fn device_hints() {
+        let bots = Bots::default();
+        assert_eq!(
+            bots.device_hint("Mozilla/5.0 (iPhone; CPU iPhone OS 9_1 like Mac OS X) AppleWebKit/601.1.46 (KHTML, like Gecko) Version/9.0 Mobile/13B143 Safari/601.1 (compatible; AdsBot-Google-Mobile; +http://www.google.com/mobile/adsbot.html)"),
+            Some(DeviceType::Mobile)
+        );
+        assert_eq!(
+            bots.device_hint("Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.6099.216 Mobile Safari/537.36 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"),
+            Some(DeviceType::Mobile)
+        );
+        assert_eq!(
+            bots.device_hint(
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            ),
+            Some(DeviceType::Desktop)
+        );
+        assert_eq!(
+            bots.device_hint("AdsBot-Google (+http://www.google.com/adsbot.html)"),
+            Some(DeviceType::Desktop)
+        );
+        assert_eq!(bots.device_hint("curl/7.0"), Some(DeviceType::Unknown));
+        assert_eq!(
+            bots.device_hint("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1"),
+            None
+        );
+    }
+}
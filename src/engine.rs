@@ -0,0 +1,102 @@
+//! Regular expression engine used to match user-agents.
+//!
+//! The `regex` crate is used by default. The smaller `regex-lite` crate is only used when the
+//! `regex-lite` feature is enabled and the default `regex` feature is not, so a dependency enabling
+//! `regex-lite` does not switch the engine of crates that keep the default features.
+
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub(crate) use regex_lite::{escape, Error, Regex};
+
+#[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+pub(crate) use regex::{escape, Error, Regex};
+
+/// Compiles a regular expression with optional size limits, in bytes.
+///
+/// The `regex-lite` engine has no lazy DFA, so it ignores the DFA size limit.
+pub(crate) fn build_regex(
+    pattern: &str,
+    size_limit: Option<usize>,
+    dfa_size_limit: Option<usize>,
+) -> Result<Regex, Error> {
+    #[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+    let mut builder = regex::RegexBuilder::new(pattern);
+    #[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+    let mut builder = regex_lite::RegexBuilder::new(pattern);
+    if let Some(limit) = size_limit {
+        builder.size_limit(limit);
+    }
+    #[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+    if let Some(limit) = dfa_size_limit {
+        builder.dfa_size_limit(limit);
+    }
+    #[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+    let _ = dfa_size_limit;
+    builder.build()
+}
+
+/// Regular expressions matched individually against the same text
+#[derive(Debug, Clone)]
+pub(crate) struct RegexSet {
+    #[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+    set: regex::RegexSet,
+    #[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+    set: Vec<Regex>,
+}
+
+impl RegexSet {
+    pub(crate) fn new<I, S>(patterns: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        #[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+        let set = regex::RegexSet::new(patterns)?;
+        #[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+        let set = patterns
+            .into_iter()
+            .map(|pattern| Regex::new(pattern.as_ref()))
+            .collect::<Result<Vec<Regex>, Error>>()?;
+        Ok(RegexSet { set })
+    }
+
+    pub(crate) fn empty() -> Self {
+        RegexSet::new(std::iter::empty::<&str>()).unwrap()
+    }
+
+    /// Returns the indexes of the regular expressions matching the text, in ascending order.
+    pub(crate) fn matches(&self, text: &str) -> Vec<usize> {
+        #[cfg(not(all(feature = "regex-lite", not(feature = "regex"))))]
+        return self.set.matches(text).into_iter().collect();
+        #[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+        return self
+            .set
+            .iter()
+            .enumerate()
+            .filter(|(_, regex)| regex.is_match(text))
+            .map(|(index, _)| index)
+            .collect();
+    }
+}
+
+#[cfg(all(test, feature = "regex-lite", feature = "include-default-bots"))]
+mod tests {
+    use crate::{Bots, BOT_PATTERNS};
+
+    #[test]
+    fn default_patterns_compile_with_regex_lite() {
+        let incompatible = BOT_PATTERNS
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|pattern| regex_lite::Regex::new(pattern).err().map(|e| (pattern, e)))
+            .collect::<Vec<_>>();
+        assert!(
+            incompatible.is_empty(),
+            "Default patterns not supported by regex-lite: {:?}",
+            incompatible
+        );
+
+        let bots = Bots::default();
+        assert!(bots.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+        assert!(!bots.is_bot("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// Errors returned when a [`Bots`](crate::Bots) instance cannot be constructed
+///
+/// New variants may be added in later releases, so a `match` needs a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BotsError {
+    /// More patterns were supplied than the configured maximum allows
+    TooManyPatterns {
+        /// Configured maximum number of patterns
+        limit: usize,
+        /// Number of distinct patterns supplied
+        count: usize,
+    },
+    /// A pattern is not a valid regular expression
+    InvalidPattern {
+        /// Line number of the pattern, starting at 1, when it was loaded from newline delimited text
+        line: Option<usize>,
+        /// The invalid pattern
+        pattern: String,
+        /// The error returned when compiling the pattern
+        source: crate::engine::Error,
+    },
+    /// The combined regular expression exceeds the size limit set with
+    /// [`Bots::set_size_limit`](crate::Bots::set_size_limit)
+    SizeLimitExceeded {
+        /// The error returned when compiling the combined regular expression
+        source: crate::engine::Error,
+    },
+    /// A pattern file could not be read
+    Io(std::io::Error),
+    /// The SHA-256 checksum of a pattern file does not match the expected checksum
+    ChecksumMismatch {
+        /// Expected checksum, as lowercase hexadecimal
+        expected: String,
+        /// Checksum of the file, as lowercase hexadecimal
+        actual: String,
+    },
+}
+
+impl fmt::Display for BotsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotsError::TooManyPatterns { limit, count } => {
+                write!(f, "{} bot patterns exceed the maximum of {}", count, limit)
+            }
+            BotsError::InvalidPattern {
+                line: Some(line),
+                pattern,
+                source,
+            } => write!(
+                f,
+                "Invalid bot pattern '{}' on line {}: {}",
+                pattern, line, source
+            ),
+            BotsError::InvalidPattern {
+                line: None,
+                pattern,
+                source,
+            } => write!(f, "Invalid bot pattern '{}': {}", pattern, source),
+            BotsError::SizeLimitExceeded { source } => {
+                write!(f, "Bot patterns exceed the size limit: {}", source)
+            }
+            BotsError::Io(source) => write!(f, "Unable to read bot patterns: {}", source),
+            BotsError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Bot pattern checksum {} does not match the expected checksum {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BotsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BotsError::InvalidPattern { source, .. } => Some(source),
+            BotsError::SizeLimitExceeded { source } => Some(source),
+            BotsError::Io(source) => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::engine::Regex;
+use crate::Bots;
+
+/// Detection quality of a [`Bots`] instance over a labeled corpus, see [`Bots::evaluate`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Evaluation<'a> {
+    /// Number of bots detected as bots
+    pub true_positives: usize,
+    /// Number of browsers not detected as bots
+    pub true_negatives: usize,
+    /// Browsers wrongly detected as bots
+    pub false_positives: Vec<&'a str>,
+    /// Bots that were not detected
+    pub false_negatives: Vec<&'a str>,
+}
+
+impl Evaluation<'_> {
+    /// Fraction of detected bots that are bots, or `0.0` if nothing was detected.
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives.len(),
+        )
+    }
+
+    /// Fraction of bots that were detected, or `0.0` if the corpus has no bots.
+    pub fn recall(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives.len(),
+        )
+    }
+
+    /// Harmonic mean of precision and recall, or `0.0` if both are zero.
+    pub fn f1(&self) -> f64 {
+        let (precision, recall) = (self.precision(), self.recall());
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+}
+
+/// User-agents of a corpus split by which of two [`Bots`] instances detect them, see
+/// [`Bots::coverage_comparison`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageComparison<'a> {
+    /// User-agents detected by both instances
+    pub both: Vec<&'a str>,
+    /// User-agents only detected by the compared instance
+    pub only_self: Vec<&'a str>,
+    /// User-agents only detected by the other instance
+    pub only_other: Vec<&'a str>,
+    /// User-agents detected by neither instance
+    pub neither: Vec<&'a str>,
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+impl Bots {
+    /// Evaluates detection against a corpus of user-agents labeled `true` for bots and `false` for browsers.
+    ///
+    /// Evaluate two configurations over the same corpus to compare pattern lists by precision,
+    /// recall, and F1 score rather than by guesswork.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("bot");
+    /// let corpus = [
+    ///     ("Googlebot/2.1", true),
+    ///     ("curl/7.64.1", true),
+    ///     ("Mozilla/5.0 (Robotic Browser)", false),
+    ///     ("Mozilla/5.0 (Windows NT 10.0; Win64; x64)", false),
+    /// ];
+    ///
+    /// let evaluation = bots.evaluate(&corpus);
+    /// assert_eq!(evaluation.precision(), 0.5);
+    /// assert_eq!(evaluation.recall(), 0.5);
+    /// assert_eq!(evaluation.false_negatives, vec!["curl/7.64.1"]);
+    /// ```
+    pub fn evaluate<'a>(&self, labeled_corpus: &[(&'a str, bool)]) -> Evaluation<'a> {
+        let mut evaluation = Evaluation::default();
+        for &(user_agent, is_bot) in labeled_corpus {
+            match (is_bot, self.is_bot(user_agent)) {
+                (true, true) => evaluation.true_positives += 1,
+                (false, false) => evaluation.true_negatives += 1,
+                (false, true) => evaluation.false_positives.push(user_agent),
+                (true, false) => evaluation.false_negatives.push(user_agent),
+            }
+        }
+        evaluation
+    }
+
+    /// Compares which user-agents of a corpus this instance and `other` detect, such as before
+    /// switching from a custom pattern list to the default patterns.
+    ///
+    /// Every user-agent of the corpus is in exactly one bucket, in corpus order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let custom = Bots::new("^curl/\ncustomnewtestb0t");
+    /// let comparison = custom.coverage_comparison(&Bots::default(), &["curl/7.64.1", "Googlebot/2.1"]);
+    ///
+    /// assert_eq!(comparison.both, vec!["curl/7.64.1"]);
+    /// assert_eq!(comparison.only_other, vec!["Googlebot/2.1"]);
+    /// ```
+    pub fn coverage_comparison<'a>(
+        &self,
+        other: &Bots,
+        corpus: &[&'a str],
+    ) -> CoverageComparison<'a> {
+        let mut comparison = CoverageComparison::default();
+        for &user_agent in corpus {
+            match (self.is_bot(user_agent), other.is_bot(user_agent)) {
+                (true, true) => comparison.both.push(user_agent),
+                (true, false) => comparison.only_self.push(user_agent),
+                (false, true) => comparison.only_other.push(user_agent),
+                (false, false) => comparison.neither.push(user_agent),
+            }
+        }
+        comparison
+    }
+
+    /// Returns the fraction of a corpus of browser user-agents matched by a single pattern, as a
+    /// measure of its false positive risk, or `0.0` for an empty corpus.
+    ///
+    /// A candidate for the default patterns should have a ratio of zero, and anything above a small
+    /// threshold is a dangerous pattern. The pattern is converted to lowercase and compiled on its
+    /// own, and matched against the lowercase user-agents. The crate's own browser corpus is
+    /// `fixtures/browsers.txt` in the repository.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let browsers = [
+    ///     "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0",
+    ///     "Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1",
+    /// ];
+    ///
+    /// assert_eq!(Bots::browser_overlap_ratio("^googlebot-image/", &browsers), 0.0);
+    /// assert_eq!(Bots::browser_overlap_ratio("mozilla", &browsers), 0.5);
+    /// ```
+    pub fn browser_overlap_ratio(pattern: &str, browsers: &[&str]) -> f64 {
+        let regex = Regex::new(&pattern.to_ascii_lowercase()).expect("Invalid pattern");
+        let matched = browsers
+            .iter()
+            .filter(|browser| regex.is_match(&browser.to_ascii_lowercase()))
+            .count();
+        ratio(matched, browsers.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn evaluate_labeled_corpus() {
+        let bots = Bots::new("bot\n^curl");
+        let corpus = [
+            ("Googlebot/2.1", true),
+            ("DuckDuckBot/1.0", true),
+            ("curl/7.64.1", true),
+            ("python-requests/2.28", true),
+            ("Mozilla/5.0 (Robotics Lab Kiosk)", false),
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64)", false),
+            ("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)", false),
+        ];
+        let evaluation = bots.evaluate(&corpus);
+
+        assert_eq!(evaluation.true_positives, 3);
+        assert_eq!(evaluation.true_negatives, 2);
+        assert_eq!(
+            evaluation.false_positives,
+            vec!["Mozilla/5.0 (Robotics Lab Kiosk)"]
+        );
+        assert_eq!(evaluation.false_negatives, vec!["python-requests/2.28"]);
+        assert_eq!(evaluation.precision(), 0.75);
+        assert_eq!(evaluation.recall(), 0.75);
+        assert_eq!(evaluation.f1(), 0.75);
+    }
+
+    #[test]
+    fn evaluate_empty_corpus() {
+        let evaluation = Bots::default().evaluate(&[]);
+        assert_eq!(evaluation.precision(), 0.0);
+        assert_eq!(evaluation.recall(), 0.0);
+        assert_eq!(evaluation.f1(), 0.0);
+    }
+
+    #[test]
+    fn coverage_comparison_partitions_corpus() {
+        let custom = Bots::new("^curl/\ncustomnewtestb0t");
+        let bundled = Bots::new("^curl/\ngooglebot");
+        let corpus = [
+            "curl/7.64.1",
+            "Mozilla/5.0 (CustomNewTestB0T /1.2)",
+            "Googlebot/2.1",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64)",
+            "curl/8.0",
+        ];
+        let comparison = custom.coverage_comparison(&bundled, &corpus);
+        assert_eq!(comparison.both, vec!["curl/7.64.1", "curl/8.0"]);
+        assert_eq!(
+            comparison.only_self,
+            vec!["Mozilla/5.0 (CustomNewTestB0T /1.2)"]
+        );
+        assert_eq!(comparison.only_other, vec!["Googlebot/2.1"]);
+        assert_eq!(
+            comparison.neither,
+            vec!["Mozilla/5.0 (Windows NT 10.0; Win64; x64)"]
+        );
+        assert_eq!(
+            comparison.both.len()
+                + comparison.only_self.len()
+                + comparison.only_other.len()
+                + comparison.neither.len(),
+            corpus.len()
+        );
+
+        let reversed = bundled.coverage_comparison(&custom, &corpus);
+        assert_eq!(reversed.only_self, comparison.only_other);
+        assert_eq!(reversed.only_other, comparison.only_self);
+    }
+
+    #[test]
+    fn browser_overlap_ratio() {
+        let browsers = include_str!("../fixtures/browsers.txt")
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            Bots::browser_overlap_ratio(r"^googlebot-image/", &browsers),
+            0.0
+        );
+        assert_eq!(
+            Bots::browser_overlap_ratio("customnewtestb0t", &browsers),
+            0.0
+        );
+        assert!(Bots::browser_overlap_ratio("mozilla", &browsers) > 0.9);
+
+        let chrome = Bots::browser_overlap_ratio("chrome/", &browsers);
+        assert!(chrome > 0.1 && chrome < 1.0, "{}", chrome);
+        assert_eq!(Bots::browser_overlap_ratio("mozilla", &[]), 0.0);
+    }
+}
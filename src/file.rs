@@ -0,0 +1,96 @@
+use crate::Bots;
+use std::{fs, io, path::Path};
+
+impl Bots {
+    /// Constructs a new instance from a file of newline delimited patterns, in the format of
+    /// [`Bots::new`].
+    ///
+    /// Blank lines are skipped. Returns an error of kind [`io::ErrorKind::InvalidData`] if a pattern is
+    /// not a valid regular expression, wrapping a [`BotsError::InvalidPattern`](crate::BotsError::InvalidPattern)
+    /// with the line number of the first invalid pattern.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::load_from_path("bot_patterns.txt").unwrap();
+    /// ```
+    pub fn load_from_path(path: impl AsRef<Path>) -> io::Result<Bots> {
+        let contents = fs::read_to_string(path)?;
+        Bots::try_new(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the loaded patterns to a file, sorted and newline delimited, so it can be loaded again
+    /// with [`Bots::load_from_path`].
+    ///
+    /// Patterns are written as stored, in lowercase and with directives already applied. Disabled
+    /// patterns and the patterns of a shared base, see [`Bots::with_shared_base`], are not written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.remove(&["Chrome-Lighthouse"]);
+    /// bots.save_patterns("bot_patterns.txt").unwrap();
+    /// ```
+    pub fn save_patterns(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut patterns: Vec<&str> = self.iter().collect();
+        patterns.sort_unstable();
+        let mut contents = patterns.join("\n");
+        contents.push('\n');
+        fs::write(path, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, BotsError};
+    use std::{fs, io};
+
+    #[test]
+    fn save_and_load_patterns() {
+        let path = std::env::temp_dir().join(format!("isbot-patterns-{}.txt", std::process::id()));
+        let mut bots = Bots::new("googlebot\n^curl/\n!anchor go-http-client/");
+        bots.append(&["CustomNewTestB0T"]);
+        bots.save_patterns(&path).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            saved,
+            "^(?:go-http-client/)\n^curl/\ncustomnewtestb0t\ngooglebot\n"
+        );
+
+        let loaded = Bots::load_from_path(&path).unwrap();
+        assert_eq!(loaded.user_agent_patterns, bots.user_agent_patterns);
+        assert!(loaded.is_bot("Go-http-client/1.1"));
+
+        fs::write(&path, "googlebot\n\n  \n(unclosed\n").unwrap();
+        let error = Bots::load_from_path(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        fs::write(&path, "googlebot\n[unclosed\n^curl/\n").unwrap();
+        let error = Bots::load_from_path(&path).unwrap_err();
+        assert!(error.to_string().contains("on line 2"), "{}", error);
+        match error
+            .into_inner()
+            .unwrap()
+            .downcast::<BotsError>()
+            .map(|e| *e)
+        {
+            Ok(BotsError::InvalidPattern { line, pattern, .. }) => {
+                assert_eq!(line, Some(2));
+                assert_eq!(pattern, "[unclosed");
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            Bots::load_from_path(&path).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
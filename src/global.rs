@@ -0,0 +1,92 @@
+use crate::{Bots, BotsError};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Patterns used by [`crate::Bots::default`] instead of the compiled-in list, when set
+static DEFAULT_OVERRIDE: RwLock<Option<Arc<str>>> = RwLock::new(None);
+
+/// Shared default instance returned by [`Bots::global`], constructed on first use
+static GLOBAL: OnceLock<Bots> = OnceLock::new();
+
+impl Bots {
+    /// Returns a shared instance with the default patterns, constructed the first time it is called.
+    ///
+    /// Constructing [`Bots::default`] compiles a large regular expression, so use this instead of
+    /// constructing an instance per request or per handler. The instance is `Sync` and can be used
+    /// from any thread; concurrent first calls construct it only once. It cannot be changed, so
+    /// construct a separate instance to append or remove patterns. Patterns set with
+    /// [`set_global_default_patterns`] are only used if they were set before the first call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// assert!(Bots::global().is_bot("Googlebot/2.1"));
+    /// assert!(!Bots::global().is_bot("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"));
+    /// ```
+    pub fn global() -> &'static Bots {
+        GLOBAL.get_or_init(Bots::default)
+    }
+}
+
+/// Replaces the patterns used by every later [`Bots::default`](crate::Bots::default) call in the
+/// process, such as to try a new pattern list pushed through configuration without a new release.
+///
+/// The text uses the same format as [`Bots::new`](crate::Bots::new). The override is stored behind a
+/// lock, so it can be set from any thread, and a `default()` call on another thread sees either the
+/// previous or the new list. Instances that were already constructed are not changed. Use
+/// [`reset_global_default`] to restore the compiled-in list.
+///
+/// The patterns are compiled before they are stored, like [`Bots::try_new`](crate::Bots::try_new),
+/// so an invalid list returns an error and leaves the current override in place.
+///
+/// # Example
+///
+/// ```
+/// use isbot::Bots;
+///
+/// isbot::set_global_default_patterns("customnewtestb0t").unwrap();
+/// assert!(Bots::default().is_bot("CustomNewTestB0T/1.0"));
+///
+/// assert!(isbot::set_global_default_patterns("valid\n(invalid").is_err());
+/// assert!(Bots::default().is_bot("CustomNewTestB0T/1.0"));
+///
+/// isbot::reset_global_default();
+/// assert!(!Bots::default().is_bot("CustomNewTestB0T/1.0"));
+/// ```
+pub fn set_global_default_patterns(text: &str) -> Result<(), BotsError> {
+    Bots::try_new(text)?;
+    *DEFAULT_OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::from(text));
+    Ok(())
+}
+
+/// Restores the compiled-in patterns for later [`Bots::default`](crate::Bots::default) calls, undoing
+/// [`set_global_default_patterns`].
+pub fn reset_global_default() {
+    *DEFAULT_OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Returns the patterns set with [`set_global_default_patterns`], if any.
+pub(crate) fn default_override() -> Option<Arc<str>> {
+    DEFAULT_OVERRIDE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::Bots;
+    use std::thread;
+
+    #[test]
+    fn global_is_shared() {
+        let instances: Vec<usize> = (0..4)
+            .map(|_| thread::spawn(|| Bots::global() as *const Bots as usize))
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert!(instances.iter().all(|&address| address == instances[0]));
+        assert!(std::ptr::eq(Bots::global(), Bots::global()));
+        assert!(Bots::global().is_bot("Googlebot/2.1"));
+    }
+}
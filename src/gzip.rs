@@ -0,0 +1,88 @@
+use crate::Bots;
+use flate2::read::MultiGzDecoder;
+use std::io::{BufRead, BufReader, Read};
+
+impl Bots {
+    /// Decompresses a gzipped stream of user-agents, one per line, and yields each user-agent with
+    /// its [`Bots::is_bot`] verdict, such as to scan rotated `.gz` access logs without writing them
+    /// to disk.
+    ///
+    /// Concatenated gzip members are read as one stream. Lines that are not valid UTF-8 are converted
+    /// lossily, and blank lines are skipped. The iterator ends at the first read error, so a truncated
+    /// or corrupt stream yields the lines decompressed before the error instead of panicking.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    /// use std::fs::File;
+    ///
+    /// let bots = Bots::default();
+    /// let log = File::open("user_agents.log.gz").unwrap();
+    /// let bot_count = bots.scan_gzip_reader(log).filter(|(_, is_bot)| *is_bot).count();
+    /// ```
+    pub fn scan_gzip_reader<'a, R: Read + 'a>(
+        &'a self,
+        reader: R,
+    ) -> impl Iterator<Item = (String, bool)> + 'a {
+        let mut lines = BufReader::new(MultiGzDecoder::new(reader));
+        let mut line = Vec::new();
+        std::iter::from_fn(move || loop {
+            line.clear();
+            match lines.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => {
+                    let user_agent = String::from_utf8_lossy(&line);
+                    let user_agent = user_agent.trim_end_matches(['\r', '\n']);
+                    if !user_agent.trim().is_empty() {
+                        return Some((user_agent.to_string(), self.is_bot(user_agent)));
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn scan_gzip_reader() {
+        let bots = Bots::new("googlebot\n^curl/");
+        let log = gzip("Googlebot/2.1\r\nMozilla/5.0 (X11; Linux x86_64)\n\ncurl/7.64.1");
+        let verdicts: Vec<(String, bool)> = bots.scan_gzip_reader(Cursor::new(log)).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                ("Googlebot/2.1".to_string(), true),
+                ("Mozilla/5.0 (X11; Linux x86_64)".to_string(), false),
+                ("curl/7.64.1".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_corrupt_gzip_reader() {
+        let bots = Bots::new("googlebot");
+        let log = gzip(&"Googlebot/2.1\n".repeat(1000));
+        let truncated = log[..log.len() / 2].to_vec();
+        assert!(bots
+            .scan_gzip_reader(Cursor::new(truncated))
+            .all(|(user_agent, is_bot)| user_agent == "Googlebot/2.1" && is_bot));
+
+        assert_eq!(
+            bots.scan_gzip_reader(Cursor::new(b"not gzip".to_vec()))
+                .count(),
+            0
+        );
+    }
+}
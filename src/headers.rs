@@ -0,0 +1,79 @@
+use crate::Bots;
+use http::{header::USER_AGENT, HeaderMap};
+
+impl Bots {
+    /// Returns `true` if the `User-Agent` header is a known bot.
+    ///
+    /// Every `User-Agent` header is checked, and values merged by a proxy are split with
+    /// [`Bots::is_bot_multivalue`]. Invalid UTF-8 sequences are replaced with `U+FFFD`, as in
+    /// [`Bots::is_bot_bytes`]. A missing `User-Agent` header is not a bot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::{header::USER_AGENT, HeaderMap, HeaderValue};
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    /// let mut headers = HeaderMap::new();
+    /// assert!(!bots.is_bot_headers(&headers));
+    ///
+    /// headers.insert(USER_AGENT, HeaderValue::from_static("Googlebot/2.1"));
+    /// assert!(bots.is_bot_headers(&headers));
+    /// ```
+    pub fn is_bot_headers(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(USER_AGENT)
+            .iter()
+            .any(|value| self.is_bot_multivalue(&String::from_utf8_lossy(value.as_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+    use http::{header::USER_AGENT, HeaderMap, HeaderValue};
+
+    fn headers(user_agent: &[u8]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_bytes(user_agent).unwrap());
+        headers
+    }
+
+    #[test]
+    fn bot_headers() {
+        let bots = Bots::default();
+        assert!(bots.is_bot_headers(&headers(
+            b"Mozilla/5.0 (compatible; Bingbot/2.0; +http://www.bing.com/bingbot.htm)"
+        )));
+        assert!(!bots.is_bot_headers(&headers(b"Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36")));
+    }
+
+    #[test]
+    fn non_utf8_headers() {
+        let bots = Bots::default();
+        assert!(bots.is_bot_headers(&headers(b"Googlebot/2.1 \xff\xfe")));
+        assert!(!bots.is_bot_headers(&headers(b"Mozilla/5.0 \xff\xfe")));
+    }
+
+    #[test]
+    fn missing_header() {
+        let bots = Bots::default();
+        assert!(!bots.is_bot_headers(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn repeated_and_merged_headers() {
+        let bots = Bots::default();
+        let browser = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+
+        let mut repeated = HeaderMap::new();
+        repeated.append(USER_AGENT, HeaderValue::from_static(browser));
+        assert!(!bots.is_bot_headers(&repeated));
+        repeated.append(USER_AGENT, HeaderValue::from_static("Googlebot/2.1"));
+        assert!(bots.is_bot_headers(&repeated));
+
+        let merged = format!("{}, Googlebot/2.1", browser);
+        assert!(bots.is_bot_headers(&headers(merged.as_bytes())));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::engine::Regex;
+use crate::Bots;
+use std::sync::OnceLock;
+
+/// Load the default headless browser patterns from a local file, unless the default bots feature is disabled
+#[cfg(feature = "include-default-bots")]
+const BOT_HEADLESS: &str = include_str!("bot_headless.txt");
+
+/// Do not load any default headless browser patterns into the compiled library if feature is not enabled
+#[cfg(not(feature = "include-default-bots"))]
+const BOT_HEADLESS: &str = "";
+
+impl Bots {
+    /// Returns `true` if the user-agent is a headless browser or browser automation tool, such as
+    /// `HeadlessChrome`, PhantomJS, Puppeteer, Playwright, or Selenium.
+    ///
+    /// Headless browsers are matched with the patterns in [bot_headless.txt](https://github.com/BryanMorgan/isbot/blob/main/src/bot_headless.txt),
+    /// independent of the patterns and allowlist of the instance, so search crawlers can be allowed
+    /// while automation is blocked. Normalizers are applied to the user-agent first, and the bundled
+    /// patterns are matched against the lowercase user-agent, even in a case-sensitive instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_headless("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36"));
+    /// assert!(!bots.is_headless("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"));
+    /// ```
+    pub fn is_headless(&self, user_agent: &str) -> bool {
+        let regex = default_headless();
+        self.with_normalized_user_agent(user_agent, |user_agent| {
+            regex.is_match(&self.lowercase_normalized(user_agent))
+        })
+    }
+}
+
+fn default_headless() -> &'static Regex {
+    static DEFAULT_HEADLESS: OnceLock<Regex> = OnceLock::new();
+    DEFAULT_HEADLESS.get_or_init(|| to_regex(BOT_HEADLESS))
+}
+
+fn to_regex(entries: &str) -> Regex {
+    let patterns: Vec<&str> = entries
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let pattern = if patterns.is_empty() {
+        r"[^\s\S]".to_string()
+    } else {
+        patterns.join("|")
+    };
+    Regex::new(&pattern).expect("Invalid headless browser pattern")
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn headless_browsers() {
+        let bots = Bots::default();
+        for user_agent in [
+            "HeadlessChrome/100.0",
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36",
+            "Mozilla/5.0 (Unknown; Linux x86_64) AppleWebKit/538.1 (KHTML, like Gecko) PhantomJS/2.1.1 Safari/538.1",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Playwright/1.40.0",
+        ] {
+            assert!(bots.is_headless(user_agent), "{}", user_agent);
+        }
+
+        for user_agent in [
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/100.0.4896.75 Safari/537.36",
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        ] {
+            assert!(!bots.is_headless(user_agent), "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn independent_of_instance_patterns() {
+        let bots = Bots::new("googlebot");
+        assert!(!bots.is_bot("HeadlessChrome/100.0"));
+        assert!(bots.is_headless("HeadlessChrome/100.0"));
+    }
+
+    #[test]
+    fn case_sensitive_instance() {
+        let bots = Bots::new_case_sensitive("^ABC/");
+        assert!(bots.is_headless("HeadlessChrome/100.0"));
+        assert!(bots.is_headless("Mozilla/5.0 (Unknown; Linux x86_64) AppleWebKit/538.1 (KHTML, like Gecko) PhantomJS/2.1.1 Safari/538.1"));
+        assert!(!bots.is_headless("ABC/1.0"));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Bots;
+use std::{fmt, sync::Arc};
+
+type DetectionCallback = dyn Fn(&str, &str) + Send + Sync;
+
+/// Callback invoked with the user-agent and matched pattern of every detected bot
+#[derive(Clone)]
+pub(crate) struct DetectionHook(Arc<DetectionCallback>);
+
+impl fmt::Debug for DetectionHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DetectionHook")
+    }
+}
+
+impl Bots {
+    /// Registers a callback invoked with the user-agent and matched pattern whenever [`Bots::is_bot`]
+    /// returns `true`, such as to log or sample detections in one place.
+    ///
+    /// The matched pattern is the one returned by [`Bots::which_bot`], including weak and
+    /// case-sensitive patterns, or an empty string for an empty user-agent detected with
+    /// [`Bots::set_empty_is_bot`]. Finding the pattern matches the
+    /// patterns individually, so it only happens for detected bots and only while a callback is
+    /// registered. A new callback replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("googlebot\nbingbot");
+    /// bots.on_detection(|user_agent, pattern| println!("{} matched {}", user_agent, pattern));
+    ///
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    pub fn on_detection(&mut self, hook: impl Fn(&str, &str) + Send + Sync + 'static) {
+        self.detection_hook = Some(DetectionHook(Arc::new(hook)));
+    }
+
+    pub(crate) fn notify_detection(&self, user_agent: &str) {
+        if let Some(DetectionHook(hook)) = &self.detection_hook {
+            let normalized = self.normalize_user_agent(user_agent);
+            hook(
+                user_agent,
+                self.detected_pattern(user_agent, &normalized).unwrap_or(""),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn detection_hook() {
+        let detections = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&detections);
+        let mut bots = Bots::new("googlebot\n^googlebot-image/\nbingbot");
+        bots.on_detection(move |user_agent, pattern| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((user_agent.to_string(), pattern.to_string()))
+        });
+
+        assert!(bots.is_bot("Googlebot-Image/1.0"));
+        assert!(!bots.is_bot("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0"));
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; bingbot/2.0)"));
+
+        assert_eq!(
+            *detections.lock().unwrap(),
+            [
+                (
+                    "Googlebot-Image/1.0".to_string(),
+                    "^googlebot-image/".to_string()
+                ),
+                (
+                    "Mozilla/5.0 (compatible; bingbot/2.0)".to_string(),
+                    "bingbot".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn detection_hook_with_case_sensitive_pattern() {
+        let detections = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&detections);
+        let mut bots = Bots::new("googlebot");
+        bots.append_case_sensitive(&["^KoalaClient/"]);
+        bots.on_detection(move |_, pattern| recorded.lock().unwrap().push(pattern.to_string()));
+
+        assert!(bots.is_bot("KoalaClient/1.0"));
+        assert_eq!(*detections.lock().unwrap(), ["^KoalaClient/"]);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{Bots, BotsError};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+
+/// Exported state of a single bot user-agent pattern, see [`Bots::to_json`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternRecord {
+    /// Lowercased regular expression
+    pub pattern: String,
+    /// Category of the pattern, if it is categorized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// `false` if the pattern was disabled with [`Bots::disable`]
+    pub enabled: bool,
+    /// Provenance tag set with [`Bots::set_tag`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl Bots {
+    /// Returns every pattern with its category, enabled status, and tag as a JSON array sorted by pattern.
+    ///
+    /// This is the structured counterpart to the newline delimited pattern list, suited to
+    /// tooling such as pattern management interfaces. Use [`Bots::from_json`] to load it back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("heritrix\nchrome-lighthouse");
+    /// bots.disable(&["chrome-lighthouse"]);
+    /// bots.set_tag("heritrix", "archive");
+    ///
+    /// assert_eq!(
+    ///     bots.to_json(),
+    ///     r#"[{"pattern":"chrome-lighthouse","enabled":false},{"pattern":"heritrix","category":"archiver","enabled":true,"tag":"archive"}]"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Pattern records are always serializable")
+    }
+
+    /// Returns the pattern records exported by [`Bots::to_json`], sorted by pattern.
+    fn pattern_records(&self) -> Vec<PatternRecord> {
+        let mut records = self
+            .user_agent_patterns
+            .iter()
+            .map(|pattern| (pattern, true))
+            .chain(
+                self.disabled_patterns
+                    .iter()
+                    .map(|pattern| (pattern, false)),
+            )
+            .map(|(pattern, enabled)| PatternRecord {
+                pattern: pattern.clone(),
+                category: self.pattern_category(pattern).map(|c| c.to_string()),
+                enabled,
+                tag: self.tags.get(pattern).cloned(),
+            })
+            .collect::<Vec<PatternRecord>>();
+        records.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+        records
+    }
+
+    /// Constructs a new instance from the JSON array produced by [`Bots::to_json`].
+    ///
+    /// Categories are derived from the patterns, so an exported `category` is informational.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::from_json(r#"[{"pattern":"googlebot","enabled":true,"tag":"search"}]"#).unwrap();
+    ///
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// assert_eq!(bots.tag("googlebot"), Some("search"));
+    /// ```
+    pub fn from_json(json: &str) -> Result<Bots, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Constructs a new instance from pattern records, compiling the regular expressions.
+    fn from_pattern_records(records: Vec<PatternRecord>) -> Result<Bots, BotsError> {
+        let mut enabled = HashSet::new();
+        let mut disabled = HashSet::new();
+        let mut tags = Vec::new();
+        for record in records {
+            let pattern = record.pattern.to_ascii_lowercase();
+            if let Some(tag) = record.tag {
+                tags.push((pattern.clone(), tag));
+            }
+            if record.enabled {
+                enabled.insert(pattern);
+            } else {
+                disabled.insert(pattern);
+            }
+        }
+
+        let mut bots = Bots::try_from_patterns(enabled)?;
+        bots.disabled_patterns = disabled;
+        bots.tags.extend(tags);
+        Ok(bots)
+    }
+}
+
+/// Serializes the patterns, with their enabled status and tags, as the JSON array of [`Bots::to_json`].
+/// The compiled regular expressions are not serialized.
+impl Serialize for Bots {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.pattern_records().serialize(serializer)
+    }
+}
+
+/// Deserializes the patterns written by the [`Serialize`] implementation and compiles the regular
+/// expressions, failing if a pattern is not a valid regular expression.
+impl<'de> Deserialize<'de> for Bots {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let records = Vec::<PatternRecord>::deserialize(deserializer)?;
+        Bots::from_pattern_records(records).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn json_round_trip() {
+        let mut bots = Bots::default();
+        bots.disable(&["Chrome-Lighthouse", "heritrix"]);
+        bots.set_tag("google-", "search");
+        bots.set_tag("heritrix", "archive");
+
+        let json = bots.to_json();
+        let loaded = Bots::from_json(&json).unwrap();
+
+        assert_eq!(loaded.user_agent_patterns, bots.user_agent_patterns);
+        assert_eq!(loaded.disabled_patterns, bots.disabled_patterns);
+        assert_eq!(loaded.tags, bots.tags);
+        assert_eq!(loaded.to_json(), json);
+        assert!(loaded.is_bot("Googlebot/2.1"));
+        assert!(!loaded.is_bot("Chrome-Lighthouse"));
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(Bots::from_json("{").is_err());
+        assert!(Bots::from_json(r#"[{"pattern":"googlebot"}]"#).is_err());
+        assert!(Bots::from_json(r#"[{"pattern":"(unclosed","enabled":true}]"#).is_err());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut bots = Bots::default();
+        bots.append(&["CustomNewTestB0T"]);
+        bots.remove(&["Chrome-Lighthouse"]);
+        bots.disable(&["heritrix"]);
+
+        let json = serde_json::to_string(&bots).unwrap();
+        let loaded: Bots = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.user_agent_patterns, bots.user_agent_patterns);
+        assert_eq!(loaded.disabled_patterns, bots.disabled_patterns);
+        for user_agent in [
+            "Mozilla/5.0 (CustomNewTestB0T /1.2)",
+            "Mozilla/5.0 (X11; Linux x86_64) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse",
+            "Mozilla/5.0 (compatible; heritrix/3.4.0 +http://example.org)",
+            "Googlebot/2.1",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0",
+        ] {
+            assert_eq!(
+                loaded.is_bot(user_agent),
+                bots.is_bot(user_agent),
+                "{}",
+                user_agent
+            );
+        }
+    }
+}
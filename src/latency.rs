@@ -0,0 +1,103 @@
+use crate::Bots;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Percentile latencies of [`Bots::is_bot`] over a corpus, see [`Bots::latency_profile`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyProfile {
+    /// Number of timed `is_bot` calls
+    pub samples: usize,
+    /// Median latency of a single call
+    pub p50: Duration,
+    /// 95th percentile latency of a single call
+    pub p95: Duration,
+    /// 99th percentile latency of a single call
+    pub p99: Duration,
+}
+
+impl Bots {
+    /// Times [`Bots::is_bot`] for every user-agent in the corpus, `iterations` times, and returns the
+    /// percentile latencies of a single call.
+    ///
+    /// The corpus is matched once before measuring so lazily built state and per-thread caches are
+    /// warm. Each call is timed with the monotonic [`Instant`] clock. Returns a default profile with
+    /// no samples if the corpus is empty or `iterations` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    /// let profile = bots.latency_profile(&["Googlebot/2.1", "Mozilla/5.0 (X11; Linux x86_64)"], 10);
+    ///
+    /// assert_eq!(profile.samples, 20);
+    /// assert!(profile.p50 <= profile.p99);
+    /// ```
+    pub fn latency_profile(&self, corpus: &[&str], iterations: usize) -> LatencyProfile {
+        for user_agent in corpus {
+            black_box(self.is_bot(user_agent));
+        }
+
+        let mut timings = Vec::with_capacity(corpus.len() * iterations);
+        for _ in 0..iterations {
+            for user_agent in corpus {
+                let start = Instant::now();
+                black_box(self.is_bot(black_box(user_agent)));
+                timings.push(start.elapsed());
+            }
+        }
+        if timings.is_empty() {
+            return LatencyProfile::default();
+        }
+
+        timings.sort_unstable();
+        LatencyProfile {
+            samples: timings.len(),
+            p50: percentile(&timings, 50),
+            p95: percentile(&timings, 95),
+            p99: percentile(&timings, 99),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty timings
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percentile, LatencyProfile};
+    use crate::Bots;
+    use std::time::Duration;
+
+    #[test]
+    fn latency_profile_is_ordered() {
+        let bots = Bots::new("googlebot\nbingbot\n^curl/");
+        let corpus = [
+            "Googlebot/2.1 (+http://www.google.com/bot.html)",
+            "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)",
+            "curl/7.64.1",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36",
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1",
+        ];
+        let profile = bots.latency_profile(&corpus, 50);
+        assert_eq!(profile.samples, 250);
+        assert!(profile.p50 > Duration::ZERO);
+        assert!(profile.p50 <= profile.p95);
+        assert!(profile.p95 <= profile.p99);
+
+        assert_eq!(bots.latency_profile(&[], 50), LatencyProfile::default());
+        assert_eq!(bots.latency_profile(&corpus, 0), LatencyProfile::default());
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let timings: Vec<Duration> = (1..=100).map(Duration::from_nanos).collect();
+        assert_eq!(percentile(&timings, 50), Duration::from_nanos(50));
+        assert_eq!(percentile(&timings, 99), Duration::from_nanos(99));
+        assert_eq!(percentile(&timings[..1], 95), Duration::from_nanos(1));
+    }
+}
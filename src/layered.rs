@@ -0,0 +1,154 @@
+use crate::{Bots, BotsError};
+
+/// Layer of a [`LayeredBots`] that decided a verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// The overlay bot patterns or the overlay allowlist
+    Overlay,
+    /// The base bot patterns
+    Base,
+}
+
+/// Verdict of a [`LayeredBots`] including the layer that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayeredVerdict {
+    /// `true` if the user-agent is a bot
+    pub is_bot: bool,
+    /// Layer that decided the verdict, or `None` if no layer matched the user-agent
+    pub layer: Option<Layer>,
+}
+
+/// Two [`Bots`] layered with override semantics, such as organization defaults under team customizations
+///
+/// The overlay is consulted first: its allowlist suppresses matches from both layers and its bot
+/// patterns add matches. The base is only consulted when the overlay does not decide.
+///
+/// # Example
+///
+/// ```
+/// use isbot::{Bots, LayeredBots};
+///
+/// let mut layered = LayeredBots::with_override(Bots::default(), Bots::new("customnewtestb0t"));
+/// layered.allow(&["Chrome-Lighthouse"]);
+///
+/// assert!(layered.is_bot("Googlebot"));
+/// assert!(layered.is_bot("CustomNewTestB0T/1.0"));
+/// assert!(!layered.is_bot("Chrome-Lighthouse"));
+/// ```
+#[derive(Debug)]
+pub struct LayeredBots {
+    base: Bots,
+    overlay: Bots,
+    allowlist: Bots,
+}
+
+impl LayeredBots {
+    /// Constructs a new instance consulting `overlay` before `base`.
+    pub fn with_override(base: Bots, overlay: Bots) -> Self {
+        LayeredBots {
+            base,
+            overlay,
+            allowlist: Bots::empty(),
+        }
+    }
+
+    /// Appends overlay allowlist user-agent regular expressions.
+    ///
+    /// A user-agent matching the allowlist is never a bot, even if it matches the base or overlay patterns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is not a valid regular expression. Use [`LayeredBots::try_allow`] for
+    /// patterns that are not known to be valid.
+    pub fn allow(&mut self, patterns: &[&str]) {
+        self.allowlist.append(patterns)
+    }
+
+    /// Appends overlay allowlist user-agent regular expressions like [`LayeredBots::allow`], returning
+    /// an error instead of panicking if a pattern is not a valid regular expression.
+    pub fn try_allow(&mut self, patterns: &[&str]) -> Result<(), BotsError> {
+        self.allowlist.try_append(patterns)
+    }
+
+    /// Returns `true` if the user-agent is a bot according to the layers.
+    pub fn is_bot(&self, user_agent: &str) -> bool {
+        self.verdict(user_agent).is_bot
+    }
+
+    /// Returns the verdict for the user-agent and the layer that decided it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Bots, Layer, LayeredBots};
+    ///
+    /// let layered = LayeredBots::with_override(Bots::default(), Bots::new("customnewtestb0t"));
+    ///
+    /// assert_eq!(layered.verdict("Googlebot").layer, Some(Layer::Base));
+    /// assert_eq!(layered.verdict("CustomNewTestB0T/1.0").layer, Some(Layer::Overlay));
+    /// assert_eq!(layered.verdict("Mozilla/5.0").layer, None);
+    /// ```
+    pub fn verdict(&self, user_agent: &str) -> LayeredVerdict {
+        let (is_bot, layer) = if self.allowlist.is_bot(user_agent) {
+            (false, Some(Layer::Overlay))
+        } else if self.overlay.is_bot(user_agent) {
+            (true, Some(Layer::Overlay))
+        } else if self.base.is_bot(user_agent) {
+            (true, Some(Layer::Base))
+        } else {
+            (false, None)
+        };
+        LayeredVerdict { is_bot, layer }
+    }
+
+    /// Returns the base layer.
+    pub fn base(&self) -> &Bots {
+        &self.base
+    }
+
+    /// Returns the overlay layer.
+    pub fn overlay(&self) -> &Bots {
+        &self.overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, Layer, LayeredBots};
+
+    const LIGHTHOUSE: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse";
+
+    #[test]
+    fn overlay_adds_and_suppresses() {
+        let mut layered =
+            LayeredBots::with_override(Bots::default(), Bots::new(r"fancynewtestb0t\s/\d\.\d"));
+        assert!(layered.is_bot(LIGHTHOUSE));
+        layered.allow(&["Chrome-Lighthouse"]);
+
+        let verdict = layered.verdict(LIGHTHOUSE);
+        assert!(!verdict.is_bot);
+        assert_eq!(verdict.layer, Some(Layer::Overlay));
+
+        let verdict = layered.verdict("Mozilla/5.0 (FancyNewTestB0T /1.2)");
+        assert!(verdict.is_bot);
+        assert_eq!(verdict.layer, Some(Layer::Overlay));
+
+        let verdict = layered.verdict("Googlebot/2.1 (+http://www.google.com/bot.html)");
+        assert!(verdict.is_bot);
+        assert_eq!(verdict.layer, Some(Layer::Base));
+
+        let verdict = layered.verdict("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/58.0.3029.110 Safari/537.36");
+        assert!(!verdict.is_bot);
+        assert_eq!(verdict.layer, None);
+    }
+
+    #[test]
+    fn try_allow() {
+        let mut layered = LayeredBots::with_override(Bots::default(), Bots::empty());
+        assert!(layered.try_allow(&["(unclosed"]).is_err());
+        assert!(layered.is_bot(LIGHTHOUSE));
+
+        layered.try_allow(&[" Chrome-Lighthouse "]).unwrap();
+        assert!(!layered.is_bot(LIGHTHOUSE));
+    }
+}
@@ -43,11 +43,101 @@
 //! assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
 //! ```
 
-use regex::Regex;
-use std::{collections::HashSet, fmt::Debug};
+use engine::Regex;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+};
+
+mod anchoring;
+mod batch;
+mod builder;
+#[cfg(feature = "cabi")]
+pub mod cabi;
+#[cfg(feature = "cache")]
+mod cache;
+mod case;
+mod category;
+#[cfg(feature = "checksum")]
+mod checksum;
+mod device;
+mod engine;
+mod error;
+mod evaluation;
+mod file;
+mod global;
+#[cfg(feature = "gzip")]
+mod gzip;
+#[cfg(feature = "http")]
+mod headers;
+mod headless;
+mod hook;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "latency-profile")]
+mod latency;
+mod layered;
+mod limits;
+#[cfg(feature = "log")]
+mod logging;
+mod matching;
+mod merge;
+mod multivalue;
+mod normalize;
+mod pcre;
+#[cfg(feature = "http")]
+mod policy;
+mod request;
+mod resolution;
+mod scanner;
+mod scoped;
+mod separators;
+mod shadow;
+mod shared;
+mod source;
+#[cfg(feature = "tower")]
+mod tower;
+mod triage;
+mod unknown;
+mod verification;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod weak;
+
+pub use anchoring::Anchoring;
+pub use builder::{BotsBuilder, OverflowPolicy};
+#[cfg(feature = "cache")]
+pub use cache::CachedBots;
+pub use category::BotCategory;
+pub use device::DeviceType;
+pub use error::BotsError;
+pub use evaluation::{CoverageComparison, Evaluation};
+pub use global::{reset_global_default, set_global_default_patterns};
+#[cfg(feature = "serde")]
+pub use json::PatternRecord;
+#[cfg(feature = "latency-profile")]
+pub use latency::LatencyProfile;
+pub use layered::{Layer, LayeredBots, LayeredVerdict};
+pub use merge::merge_sources;
+#[cfg(feature = "http")]
+pub use policy::{BotAction, BotPolicy};
+pub use resolution::ResolutionPolicy;
+pub use scanner::BotScanner;
+pub use scoped::ScopedExclusions;
+pub use separators::parse_patterns;
+pub use shadow::{ShadowDetector, ShadowSummary};
+#[cfg(feature = "tower")]
+pub use tower::{BotFilter, BotFilterFuture, BotFilterLayer};
+pub use triage::Verdict;
 
 /// Wrapper struct to maintain bot regular expression patterns
 ///
+/// Cloning is cheap compared to constructing a new instance: the compiled regular expressions are
+/// shared rather than recompiled, while the patterns are copied so each clone can be changed
+/// independently. A detection hook, see [`Bots::on_detection`], is shared by the clones.
+///
 /// # Example
 ///
 /// ```
@@ -55,87 +145,765 @@ use std::{collections::HashSet, fmt::Debug};
 ///
 /// let bots = Bots::default();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Bots {
     user_agent_patterns: HashSet<String>,
     user_agents_regex: Regex,
+    category_regexes: HashMap<BotCategory, Regex>,
+    disabled_patterns: HashSet<String>,
+    tags: HashMap<String, String>,
+    custom_categories: HashMap<String, BotCategory>,
+    weak: weak::WeakPatterns,
+    pattern_set: OnceLock<matching::PatternSet>,
+    suffix_regex: OnceLock<Regex>,
+    normalizers: Vec<normalize::Normalizer>,
+    browser_tokens: Vec<String>,
+    request_patterns: request::RequestPatterns,
+    layers: resolution::Layers,
+    append_anchoring: Anchoring,
+    shared_base: Option<Arc<[String]>>,
+    case_sensitive: case::CaseSensitivePatterns,
+    preserve_case: bool,
+    detection_hook: Option<hook::DetectionHook>,
+    empty_is_bot: bool,
+    size_limits: limits::SizeLimits,
+}
+
+/// Result of [`Bots::remove_checked`] listing which patterns were actually removed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RemoveReport {
+    /// Lowercased patterns that were found and removed
+    pub removed: Vec<String>,
+    /// Lowercased patterns that did not match any existing entry
+    pub not_found: Vec<String>,
+}
+
+/// Result of [`Bots::new_normalized`] listing the entries collapsed into an existing pattern
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NormalizeReport {
+    /// Original entries dropped because they normalized to an already loaded pattern
+    pub collapsed: Vec<String>,
 }
 
 /// Load default bot user-agent regular expressions from a local file, unless the feature is disabled
 #[cfg(feature = "include-default-bots")]
-const BOT_PATTERNS: &str = include_str!("bot_regex_patterns.txt");
+pub(crate) const BOT_PATTERNS: &str = include_str!("bot_regex_patterns.txt");
 
 /// Do not load any default user-agent strings into the compiled library if feature is not enabled
 #[cfg(not(feature = "include-default-bots"))]
-const BOT_PATTERNS: &str = "";
+pub(crate) const BOT_PATTERNS: &str = "";
+
+impl Default for Bots {
+    /// Constructs a new instance with default user-agent patterns.
+    ///
+    /// The default patterns are compiled in, unless they were replaced for the process with
+    /// [`set_global_default_patterns`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_bot("Googlebot"));
+    /// ```
+    ///
+    /// Without the `include-default-bots` feature there are no default patterns, so the instance
+    /// never detects a bot. Use [`Bots::new`] to supply patterns or [`Bots::empty`] to explicitly
+    /// start without any.
+    fn default() -> Self {
+        match global::default_override() {
+            Some(patterns) => Bots::new(&patterns),
+            None => Bots::new(BOT_PATTERNS),
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Bots {
+    /// Constructs a new instance with one bot user-agent regular expression entry per item.
+    ///
+    /// Entries are converted to lowercase and blank entries are skipped, like the lines passed to
+    /// [`Bots::new`], and the regular expression is compiled once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let patterns = vec!["^Googlebot-Image/", "bingpreview/", ""];
+    /// let bots: Bots = patterns.into_iter().collect();
+    ///
+    /// assert!(bots.is_bot("Googlebot-Image/1.0"));
+    /// assert_eq!(bots.len(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is not a valid regular expression.
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        Bots::from_patterns(
+            iter.into_iter()
+                .filter_map(|entry| Bots::parse_line(&entry.to_ascii_lowercase()))
+                .collect(),
+        )
+    }
+}
+
+impl From<Vec<String>> for Bots {
+    /// Constructs a new instance with one bot user-agent regular expression entry per string, like
+    /// [`Bots::from_iter`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is not a valid regular expression.
+    fn from(entries: Vec<String>) -> Self {
+        entries.iter().map(String::as_str).collect()
+    }
+}
+
+impl PartialEq for Bots {
+    /// Returns `true` if both instances have the same bot user-agent patterns.
+    ///
+    /// Only the pattern entries are compared. The compiled regular expression is derived from them and
+    /// is ignored, as are other settings such as disabled patterns, the allowlist, normalizers, and
+    /// the patterns of a shared base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("googlebot");
+    /// bots.append(&["bingbot"]);
+    ///
+    /// assert_eq!(bots, Bots::new("bingbot\nGooglebot"));
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        self.user_agent_patterns == other.user_agent_patterns
+    }
+}
+
+impl Eq for Bots {}
+
+impl Bots {
+    /// Constructs a new instance with bot user-agent regular expression entries delimited by a newline
+    ///
+    /// All user-agent regular expressions are converted to lowercase and surrounding whitespace is
+    /// trimmed, so entries differing only in casing or padding are stored once. Patterns relying on a
+    /// leading or trailing space should use `[ ]` or `\s` instead.
+    ///
+    /// An entry can start with a directive to change how it matches:
+    ///
+    /// | Entry | Matches |
+    /// | ----- | ------- |
+    /// | `googlebot` | the regular expression anywhere in the user-agent |
+    /// | `!anchor googlebot` | the regular expression at the start of the user-agent, like `^googlebot` |
+    /// | `!exact curl/7.0` | the whole user-agent, compared as literal text rather than a regular expression |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let custom_user_agent_patterns = r#"
+    /// ^Googlebot-Image/
+    /// bingpreview/"#;
+    /// let bots = Bots::new(custom_user_agent_patterns);
+    ///
+    /// assert!(bots.is_bot("Googlebot-Image/1.0"));
+    /// assert!(bots.is_bot("Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/534+ (KHTML, like Gecko) BingPreview/1.0b"));
+    /// assert!(!bots.is_bot("Googlebot"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry is not a valid regular expression. Use [`Bots::try_new`] for patterns
+    /// that are not known to be valid, such as patterns loaded from a configuration file.
+    pub fn new(bot_entries: &str) -> Self {
+        Bots::try_new(bot_entries).expect("Invalid bot pattern")
+    }
+
+    /// Constructs a new instance like [`Bots::new`], returning an error instead of panicking if an
+    /// entry is not a valid regular expression.
+    ///
+    /// The error is a [`BotsError::InvalidPattern`] with the first invalid entry, its line number,
+    /// and the error returned when compiling it. Entries are only compiled one by one to find the
+    /// line after the combined regular expression fails to compile, so valid entries are not slower.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Bots, BotsError};
+    ///
+    /// let bots = Bots::try_new("googlebot\n^curl/").unwrap();
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    ///
+    /// let error = Bots::try_new("googlebot\n(unclosed").unwrap_err();
+    /// assert!(matches!(
+    ///     error,
+    ///     BotsError::InvalidPattern { line: Some(2), pattern, .. } if pattern == "(unclosed"
+    /// ));
+    /// ```
+    pub fn try_new(bot_entries: &str) -> Result<Self, BotsError> {
+        let bot_entries = bot_entries.to_ascii_lowercase();
+        Bots::try_from_patterns(Bots::parse_lines(&bot_entries))
+            .map_err(|error| Bots::validate_lines(&bot_entries).err().unwrap_or(error))
+    }
+
+    /// Constructs a new instance without any user-agent patterns.
+    ///
+    /// Use this instead of [`Bots::default`] when the `include-default-bots` feature is disabled
+    /// and patterns are added later with [`Bots::append`]. Until then no user-agent is a bot,
+    /// including an empty one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::empty();
+    /// assert!(!bots.is_bot("Googlebot"));
+    ///
+    /// bots.append(&["googlebot"]);
+    /// assert!(bots.is_bot("Googlebot"));
+    /// ```
+    pub fn empty() -> Self {
+        Bots::new("")
+    }
+
+    /// Returns a [`BotsBuilder`] to configure a new instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::builder().with_defaults().max_patterns(1000).build().unwrap();
+    /// assert!(bots.is_bot("Googlebot"));
+    /// ```
+    pub fn builder() -> BotsBuilder {
+        BotsBuilder::new()
+    }
+
+    /// Constructs a new instance like [`Bots::new`], reporting the entries collapsed because they
+    /// only differ by surrounding whitespace or casing.
+    ///
+    /// Each entry is trimmed and lowercased before it is stored. Entries that normalize to an
+    /// already loaded pattern are dropped and listed in the returned [`NormalizeReport`].
+    ///
+    /// The bundled default patterns are already normalized, so the default list uses the same
+    /// memory either way; the savings only apply to hand-edited or merged lists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let (bots, report) = Bots::new_normalized("Googlebot\n  googlebot \nbingpreview/");
+    /// assert_eq!(report.collapsed, vec!["  googlebot "]);
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    pub fn new_normalized(bot_entries: &str) -> (Self, NormalizeReport) {
+        let mut user_agent_patterns = HashSet::new();
+        let mut report = NormalizeReport::default();
+        for line in bot_entries.lines() {
+            if let Some(pattern) = Bots::parse_line(&line.trim().to_ascii_lowercase()) {
+                if !user_agent_patterns.insert(pattern) {
+                    report.collapsed.push(line.to_string());
+                }
+            }
+        }
+        (Bots::from_patterns(user_agent_patterns), report)
+    }
+
+    /// Returns `true` the user-agent is a known bot.
+    ///
+    /// The user-agent comparison is done using lowercase, unless the instance was constructed with
+    /// [`Bots::new_case_sensitive`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// assert!(!bots.is_bot("Dalvik/2.1.0 (Linux; U; Android 8.0.0; SM-G930F Build/R16NW)"));
+    /// ```    
+    pub fn is_bot(&self, user_agent: &str) -> bool {
+        let is_bot = self.detect(user_agent);
+        if is_bot && self.detection_hook.is_some() {
+            self.notify_detection(user_agent);
+        }
+        #[cfg(feature = "log")]
+        if is_bot && log::log_enabled!(log::Level::Debug) {
+            let normalized = self.normalize_user_agent(user_agent);
+            logging::log_detection(user_agent, self.detected_pattern(user_agent, &normalized));
+        }
+        is_bot
+    }
+
+    /// Returns the [`Bots::is_bot`] verdict without calling the detection hook.
+    pub(crate) fn detect(&self, user_agent: &str) -> bool {
+        if user_agent.trim().is_empty() {
+            return self.empty_is_bot;
+        }
+        self.with_normalized_user_agent(user_agent, |normalized| {
+            (self.user_agents_regex.is_match(normalized)
+                || self.weak.is_match(normalized)
+                || self.case_sensitive.is_match(user_agent))
+                && (self.layers.is_empty() || self.resolve_layers(user_agent, normalized))
+        })
+    }
+
+    /// Sets whether an empty or whitespace-only user-agent is a bot, which is `false` by default.
+    ///
+    /// Many clients that strip the `User-Agent` header are bots, so abuse prevention often treats a
+    /// missing user-agent as one. The setting is checked before any pattern is matched, so it applies
+    /// regardless of the loaded patterns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// assert!(!bots.is_bot(""));
+    ///
+    /// bots.set_empty_is_bot(true);
+    /// assert!(bots.is_bot(""));
+    /// assert!(bots.is_bot("  "));
+    /// ```
+    pub fn set_empty_is_bot(&mut self, value: bool) {
+        self.empty_is_bot = value;
+    }
+
+    /// Returns `true` if the raw user-agent bytes are a known bot.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD` before matching, so the valid parts of
+    /// the user-agent are still matched, but a pattern cannot match across an invalid sequence. The
+    /// bytes are validated once, and copied only if they contain an invalid sequence, so valid
+    /// user-agents cost the same as [`Bots::is_bot`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_bot_bytes(b"Googlebot/2.1 \xff\xfe"));
+    /// assert!(!bots.is_bot_bytes(b"Mozilla/5.0 \xff\xfe"));
+    /// ```
+    pub fn is_bot_bytes(&self, user_agent: &[u8]) -> bool {
+        self.is_bot(&String::from_utf8_lossy(user_agent))
+    }
+
+    /// Returns `true` if the fields produced by a user-agent parser, such as `woothee` or `uap-rs`, are a
+    /// known bot.
+    ///
+    /// Patterns are matched against `family` and then `product`, each treated as its own user-agent, so
+    /// patterns still match as substrings of a field and anchored patterns match at the start of a field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_bot_parsed("Googlebot", None));
+    /// assert!(bots.is_bot_parsed("Other", Some("curl")));
+    /// assert!(!bots.is_bot_parsed("Chrome", Some("Chrome Mobile")));
+    /// ```
+    pub fn is_bot_parsed(&self, family: &str, product: Option<&str>) -> bool {
+        self.is_bot(family) || product.is_some_and(|product| self.is_bot(product))
+    }
+
+    /// Returns `true` if the user-agent would be a known bot with one extra pattern, without changing
+    /// the instance.
+    ///
+    /// The extra pattern is converted to lowercase and only compiled if the current patterns do not
+    /// already match, which makes this suited to trying candidate patterns interactively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    /// assert!(bots.would_be_bot_with("Mozilla/5.0 (CustomNewTestB0T /1.2)", "customnewtestb0t").unwrap());
+    /// assert!(bots.would_be_bot_with("Mozilla/5.0 (X)", "(unclosed").is_err());
+    /// ```
+    pub fn would_be_bot_with(
+        &self,
+        user_agent: &str,
+        extra_pattern: &str,
+    ) -> Result<bool, engine::Error> {
+        if self.is_bot(user_agent) || extra_pattern.trim().is_empty() {
+            return Ok(self.is_bot(user_agent));
+        }
+        let extra = Regex::new(&self.normalize_pattern(extra_pattern))?;
+        Ok(extra.is_match(&self.normalize_user_agent(user_agent)))
+    }
+
+    /// Converts the instance into a cloneable predicate that returns `true` for a known bot user-agent.
+    ///
+    /// The instance is shared behind an [`Arc`], so clones of the predicate are cheap and can be sent to
+    /// other threads. This is useful for APIs that accept a `Fn(&str) -> bool` rather than a `Bots`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let is_bot = Bots::default().into_matcher();
+    ///
+    /// assert!(is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// assert!(!is_bot("Dalvik/2.1.0 (Linux; U; Android 8.0.0; SM-G930F Build/R16NW)"));
+    /// ```
+    pub fn into_matcher(self) -> impl Fn(&str) -> bool + Send + Sync + Clone {
+        let bots = Arc::new(self);
+        move |user_agent| bots.is_bot(user_agent)
+    }
+
+    /// Returns `true` if the user-agent is a known archiver or web preservation crawler, such as
+    /// the Internet Archive's `archive.org_bot` or `ia_archiver`.
+    ///
+    /// Archivers are also reported by [`Bots::is_bot`], so this can be used to let them through
+    /// even when other bots are blocked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_archiver("Mozilla/5.0 (compatible; archive.org_bot +http://www.archive.org/details/archive.org_bot)"));
+    /// assert!(!bots.is_archiver("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// ```
+    pub fn is_archiver(&self, user_agent: &str) -> bool {
+        self.is_category(user_agent, BotCategory::Archiver)
+    }
+
+    /// Returns `true` if the user-agent is a known browser automation framework, such as
+    /// Selenium, Playwright, Puppeteer, or WebDriver.
+    ///
+    /// Headless browsers such as `HeadlessChrome` and PhantomJS are included, since their
+    /// user-agent is usually the only tell left by an automation framework driving them.
+    /// Cypress is not detected because it runs tests in a regular Electron or Chrome browser.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_automation("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.6099.28 Safari/537.36"));
+    /// assert!(!bots.is_automation("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// ```
+    pub fn is_automation(&self, user_agent: &str) -> bool {
+        self.is_category(user_agent, BotCategory::Automation)
+    }
+
+    /// Returns `true` if the user-agent is a known e-commerce price scraper, such as `PriceGrabber`
+    /// or `Price2Spy`.
+    ///
+    /// The list is necessarily incomplete, since many scrapers spoof browser user-agents, so treat
+    /// this as one signal among many rather than proof that a request is not a price scraper.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_price_scraper("Mozilla/5.0 (compatible; Price2Spy/1.0; +https://www.price2spy.com)"));
+    /// assert!(!bots.is_price_scraper("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// ```
+    pub fn is_price_scraper(&self, user_agent: &str) -> bool {
+        self.is_category(user_agent, BotCategory::PriceScraper)
+    }
+
+    fn is_category(&self, user_agent: &str, category: BotCategory) -> bool {
+        if self.layers.excluded_categories.contains(&category) {
+            return false;
+        }
+        self.category_regexes.get(&category).is_some_and(|regex| {
+            self.with_normalized_user_agent(user_agent, |user_agent| regex.is_match(user_agent))
+        })
+    }
+
+    /// Appends bot user-agent regular expressions patterns.
+    ///
+    /// Patterns are trimmed, and blank patterns are skipped since an empty pattern would match every
+    /// user-agent. Duplicates are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    /// bots.append(&[r"CustomNewTestB0T\s/\d\.\d"]);
+    /// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    ///
+    /// let new_bot_patterns = vec!["GoogleMetaverse", "^Special/"];
+    /// bots.append(&new_bot_patterns);
+    /// assert!(bots.is_bot("Mozilla/5.0 (GoogleMetaverse/1.0)"));
+    /// ```
+    pub fn append(&mut self, bots: &[&str]) {
+        for bot in bots.iter().filter(|bot| !bot.trim().is_empty()) {
+            self.user_agent_patterns
+                .insert(self.append_anchoring.apply(&self.normalize_pattern(bot)));
+        }
+        self.update_regex()
+    }
+
+    /// Appends bot user-agent regular expressions like [`Bots::append`], returning an error instead of
+    /// panicking if a pattern is not a valid regular expression.
+    ///
+    /// Each pattern is compiled on its own before any is added, and on error the patterns are left
+    /// exactly as they were before the call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    ///
+    /// assert!(bots.try_append(&["CustomNewTestB0T", "(unclosed"]).is_err());
+    /// assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    ///
+    /// bots.try_append(&["CustomNewTestB0T"]).unwrap();
+    /// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    /// ```
+    pub fn try_append(&mut self, bots: &[&str]) -> Result<(), BotsError> {
+        let patterns: Vec<String> = bots
+            .iter()
+            .filter(|bot| !bot.trim().is_empty())
+            .map(|bot| self.append_anchoring.apply(&self.normalize_pattern(bot)))
+            .collect();
+        Bots::validate_patterns(&patterns)?;
+
+        let added: Vec<String> = patterns
+            .into_iter()
+            .filter(|pattern| self.user_agent_patterns.insert(pattern.clone()))
+            .collect();
+        self.try_update_regex().inspect_err(|_| {
+            for pattern in &added {
+                self.user_agent_patterns.remove(pattern);
+            }
+        })
+    }
+
+    /// Removes bot user-agent regular expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    ///
+    ///
+    /// assert!(bots.is_bot("Chrome-Lighthouse"));
+    /// bots.remove(&["Chrome-Lighthouse"]);
+    /// assert!(!bots.is_bot("Chrome-Lighthouse"));
+    ///
+    /// let bot_patterns_to_remove = vec!["bingpreview/", "Google Favicon"];
+    /// bots.remove(&bot_patterns_to_remove);
+    /// assert!(!bots.is_bot("Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/534+ (KHTML, like Gecko) BingPreview/1.0b"));
+    /// assert!(!bots.is_bot("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/49.0.2623.75 Safari/537.36 Google Favicon"));
+    /// ```
+    pub fn remove(&mut self, bots: &[&str]) {
+        for bot in bots {
+            self.user_agent_patterns
+                .remove(&self.normalize_pattern(bot));
+        }
+        self.update_regex()
+    }
+
+    /// Returns the number of loaded bot user-agent patterns.
+    ///
+    /// Disabled patterns and the patterns of a shared base, see [`Bots::with_shared_base`], are not
+    /// counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("googlebot\n^curl/");
+    /// assert_eq!(bots.len(), 2);
+    ///
+    /// bots.append(&["CustomNewTestB0T"]);
+    /// assert_eq!(bots.len(), 3);
+    ///
+    /// bots.remove(&["googlebot", "^curl/"]);
+    /// assert_eq!(bots.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.user_agent_patterns.len()
+    }
+
+    /// Returns an iterator over the loaded bot user-agent patterns, as stored in lowercase.
+    ///
+    /// The order is unspecified and may change between calls after patterns are added or removed, so
+    /// collect and sort the patterns for deterministic output. Disabled patterns and the patterns of a
+    /// shared base, see [`Bots::with_shared_base`], are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("googlebot\n^curl/");
+    /// let mut patterns: Vec<&str> = bots.iter().collect();
+    /// patterns.sort();
+    ///
+    /// assert_eq!(patterns, vec!["^curl/", "googlebot"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.user_agent_patterns.iter().map(String::as_str)
+    }
+
+    /// Returns the source of the combined regular expression matched by [`Bots::is_bot`], such as to
+    /// paste it into a regular expression tester.
+    ///
+    /// The patterns are joined as alternatives with `|`, sorted alphabetically, so instances with the
+    /// same patterns have the same expression in every run. Without any patterns the expression is
+    /// `\b\B`, which never matches. Weak, case-sensitive, and allow patterns are matched separately
+    /// and are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("^Googlebot/");
+    /// assert_eq!(bots.as_regex_str(), "^googlebot/");
+    ///
+    /// assert_eq!(Bots::empty().as_regex_str(), r"\b\B");
+    /// ```
+    pub fn as_regex_str(&self) -> &str {
+        self.user_agents_regex.as_str()
+    }
+
+    /// Returns `true` if the pattern is loaded, such as to check that a default pattern still exists
+    /// before removing it.
+    ///
+    /// The pattern is converted to lowercase and compared with the loaded patterns as text, unlike
+    /// [`Bots::is_bot`], which matches a user-agent against them. Disabled patterns are not loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// assert!(bots.contains("Chrome-Lighthouse"));
+    ///
+    /// bots.remove(&["Chrome-Lighthouse"]);
+    /// assert!(!bots.contains("Chrome-Lighthouse"));
+    /// ```
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.user_agent_patterns
+            .contains(&self.normalize_pattern(pattern))
+    }
 
-impl Default for Bots {
-    /// Constructs a new instance with default user-agent patterns.
+    /// Returns `true` if no bot user-agent patterns are loaded, see [`Bots::len`].
     ///
     /// # Example
     ///
     /// ```
     /// use isbot::Bots;
     ///
-    /// let bots = Bots::default();
+    /// let mut bots = Bots::empty();
+    /// assert!(bots.is_empty());
     ///
-    /// assert!(bots.is_bot("Googlebot"));
+    /// bots.append(&["googlebot"]);
+    /// assert!(!bots.is_empty());
     /// ```
-    fn default() -> Self {
-        Bots::new(BOT_PATTERNS)
+    pub fn is_empty(&self) -> bool {
+        self.user_agent_patterns.is_empty()
     }
-}
 
-impl Bots {
-    /// Constructs a new instance with bot user-agent regular expression entries delimited by a newline
+    /// Disables bot user-agent regular expressions without removing them.
     ///
-    /// All user-agent regular expressions are converted to lowercase.
+    /// Disabled patterns are no longer matched but are kept, with their tags, so they can be
+    /// re-enabled with [`Bots::enable`].
     ///
     /// # Example
     ///
     /// ```
     /// use isbot::Bots;
     ///
-    /// let custom_user_agent_patterns = r#"
-    /// ^Googlebot-Image/
-    /// bingpreview/"#;
-    /// let bots = Bots::new(custom_user_agent_patterns);
+    /// let mut bots = Bots::default();
     ///
-    /// assert!(bots.is_bot("Googlebot-Image/1.0"));
-    /// assert!(bots.is_bot("Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/534+ (KHTML, like Gecko) BingPreview/1.0b"));
-    /// assert!(!bots.is_bot("Googlebot"));
+    /// bots.disable(&["Chrome-Lighthouse"]);
+    /// assert!(!bots.is_bot("Chrome-Lighthouse"));
+    /// bots.enable(&["Chrome-Lighthouse"]);
+    /// assert!(bots.is_bot("Chrome-Lighthouse"));
     /// ```
-    pub fn new(bot_entries: &str) -> Self {
-        let user_agent_patterns = Bots::parse_lines(&bot_entries.to_ascii_lowercase());
-        let combined_user_agent_regex = Bots::to_regex(&user_agent_patterns);
-        Bots {
-            user_agent_patterns,
-            user_agents_regex: combined_user_agent_regex,
+    pub fn disable(&mut self, bots: &[&str]) {
+        for bot in bots {
+            let pattern = self.normalize_pattern(bot);
+            if self.user_agent_patterns.remove(&pattern) {
+                self.disabled_patterns.insert(pattern);
+            }
+        }
+        self.update_regex()
+    }
+
+    /// Enables bot user-agent regular expressions previously disabled with [`Bots::disable`].
+    pub fn enable(&mut self, bots: &[&str]) {
+        for bot in bots {
+            let pattern = self.normalize_pattern(bot);
+            if self.disabled_patterns.remove(&pattern) {
+                self.user_agent_patterns.insert(pattern);
+            }
         }
+        self.update_regex()
     }
 
-    /// Returns `true` the user-agent is a known bot.
-    ///
-    /// The user-agent comparison is done using lowercase.
+    /// Sets a provenance tag on a bot user-agent regular expression, such as the list it was loaded from.
     ///
     /// # Example
     ///
     /// ```
     /// use isbot::Bots;
     ///
-    /// let bots = Bots::default();
+    /// let mut bots = Bots::default();
     ///
-    /// assert!(bots.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
-    /// assert!(!bots.is_bot("Dalvik/2.1.0 (Linux; U; Android 8.0.0; SM-G930F Build/R16NW)"));
-    /// ```    
-    pub fn is_bot(&self, user_agent: &str) -> bool {
-        self.user_agents_regex
-            .is_match(&user_agent.to_ascii_lowercase())
+    /// bots.set_tag("Chrome-Lighthouse", "performance-tools");
+    /// assert_eq!(bots.tag("chrome-lighthouse"), Some("performance-tools"));
+    /// ```
+    pub fn set_tag(&mut self, pattern: &str, tag: &str) {
+        let pattern = self.normalize_pattern(pattern);
+        self.tags.insert(pattern, tag.to_string());
     }
 
-    /// Appends bot user-agent regular expressions patterns.
+    /// Returns the provenance tag of a bot user-agent regular expression.
+    pub fn tag(&self, pattern: &str) -> Option<&str> {
+        self.tags
+            .get(&self.normalize_pattern(pattern))
+            .map(String::as_str)
+    }
+
+    /// Removes bot user-agent regular expressions and reports which patterns were removed.
     ///
-    /// Duplicates are ignored.
+    /// Unlike [`Bots::remove`], patterns that did not match an existing entry are returned
+    /// in [`RemoveReport::not_found`] so a typo or formatting mismatch is not silently ignored.
     ///
     /// # Example
     ///
@@ -143,78 +911,315 @@ impl Bots {
     /// use isbot::Bots;
     ///
     /// let mut bots = Bots::default();
-    /// assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
-    /// bots.append(&[r"CustomNewTestB0T\s/\d\.\d"]);
-    /// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
     ///
-    /// let new_bot_patterns = vec!["GoogleMetaverse", "^Special/"];
-    /// bots.append(&new_bot_patterns);
-    /// assert!(bots.is_bot("Mozilla/5.0 (GoogleMetaverse/1.0)"));
+    /// let report = bots.remove_checked(&["Chrome-Lighthouse", "Chrome Lighthouse"]);
+    /// assert_eq!(report.removed, vec!["chrome-lighthouse"]);
+    /// assert_eq!(report.not_found, vec!["chrome lighthouse"]);
+    /// assert!(!bots.is_bot("Chrome-Lighthouse"));
     /// ```
-    pub fn append(&mut self, bots: &[&str]) {
+    pub fn remove_checked(&mut self, bots: &[&str]) -> RemoveReport {
+        let mut report = RemoveReport::default();
         for bot in bots {
-            self.user_agent_patterns.insert(bot.to_ascii_lowercase());
+            let pattern = self.normalize_pattern(bot);
+            if self.user_agent_patterns.remove(&pattern) {
+                report.removed.push(pattern);
+            } else {
+                report.not_found.push(pattern);
+            }
         }
-        self.update_regex()
+        if !report.removed.is_empty() {
+            self.update_regex()
+        }
+        report
     }
 
-    /// Removes bot user-agent regular expressions.
+    /// Removes every bot user-agent regular expression for which `predicate` returns `true`.
+    ///
+    /// The combined regular expression is recompiled once, and the removed patterns are returned
+    /// sorted alphabetically.
     ///
     /// # Example
     ///
     /// ```
     /// use isbot::Bots;
     ///
-    /// let mut bots = Bots::default();
+    /// let mut bots = Bots::new("google-\nbingpreview/\nmediapartners-google");
     ///
+    /// let removed = bots.remove_where(|pattern| pattern.contains("google"));
+    /// assert_eq!(removed, vec!["google-", "mediapartners-google"]);
+    /// assert!(!bots.is_bot("Google-InspectionTool/1.0"));
+    /// assert!(bots.is_bot("BingPreview/1.0b"));
+    /// ```
+    pub fn remove_where(&mut self, predicate: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut removed: Vec<String> = self
+            .user_agent_patterns
+            .iter()
+            .filter(|pattern| predicate(pattern))
+            .cloned()
+            .collect();
+        if !removed.is_empty() {
+            for pattern in &removed {
+                self.user_agent_patterns.remove(pattern);
+            }
+            self.update_regex();
+        }
+        removed.sort_unstable();
+        removed
+    }
+
+    /// Removes all bot user-agent patterns, so no user-agent is a bot until patterns are appended.
     ///
-    /// assert!(bots.is_bot("Chrome-Lighthouse"));
-    /// bots.remove(&["Chrome-Lighthouse"]);
-    /// assert!(!bots.is_bot("Chrome-Lighthouse"));
+    /// Disabled, weak, and case-sensitive patterns, tags, and categories are removed too. Settings such as
+    /// normalizers, allow patterns, and [`Bots::set_empty_is_bot`] are kept. The patterns of a shared
+    /// base, see [`Bots::with_shared_base`], are always matched and are not removed.
+    ///
+    /// # Example
     ///
-    /// let bot_patterns_to_remove = vec!["bingpreview/", "Google Favicon"];
-    /// bots.remove(&bot_patterns_to_remove);
-    /// assert!(!bots.is_bot("Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/534+ (KHTML, like Gecko) BingPreview/1.0b"));
-    /// assert!(!bots.is_bot("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/49.0.2623.75 Safari/537.36 Google Favicon"));
     /// ```
-    pub fn remove(&mut self, bots: &[&str]) {
-        for bot in bots {
-            self.user_agent_patterns.remove(&bot.to_ascii_lowercase());
-        }
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.clear();
+    /// assert!(!bots.is_bot("Googlebot/2.1"));
+    ///
+    /// bots.append(&["CustomNewTestB0T"]);
+    /// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    /// ```
+    pub fn clear(&mut self) {
+        self.user_agent_patterns.clear();
+        self.disabled_patterns.clear();
+        self.tags.clear();
+        self.custom_categories.clear();
+        self.weak = weak::WeakPatterns::default();
+        self.case_sensitive = case::CaseSensitivePatterns::default();
         self.update_regex()
     }
 
+    pub(crate) fn from_patterns(user_agent_patterns: HashSet<String>) -> Self {
+        Bots::try_from_patterns(user_agent_patterns).expect("Invalid bot pattern")
+    }
+
+    pub(crate) fn try_from_patterns(
+        user_agent_patterns: HashSet<String>,
+    ) -> Result<Self, BotsError> {
+        let combined_user_agent_regex = Bots::try_to_regex(&user_agent_patterns)?;
+        let category_regexes = Bots::to_category_regexes(&user_agent_patterns, |pattern| {
+            category::default_category(pattern).map(|category| (category, pattern.to_string()))
+        });
+        Ok(Bots {
+            user_agent_patterns,
+            user_agents_regex: combined_user_agent_regex,
+            category_regexes,
+            disabled_patterns: HashSet::new(),
+            tags: HashMap::new(),
+            custom_categories: HashMap::new(),
+            weak: weak::WeakPatterns::default(),
+            pattern_set: OnceLock::new(),
+            suffix_regex: OnceLock::new(),
+            normalizers: Vec::new(),
+            browser_tokens: unknown::DEFAULT_BROWSER_TOKENS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            request_patterns: request::RequestPatterns::default(),
+            layers: resolution::Layers::default(),
+            append_anchoring: Anchoring::None,
+            shared_base: None,
+            case_sensitive: case::CaseSensitivePatterns::default(),
+            preserve_case: false,
+            detection_hook: None,
+            empty_is_bot: false,
+            size_limits: limits::SizeLimits::default(),
+        })
+    }
+
     fn update_regex(&mut self) {
-        self.user_agents_regex = Bots::to_regex(&self.user_agent_patterns)
+        self.try_update_regex().expect("Invalid bot pattern")
+    }
+
+    /// Rebuilds the regular expressions, leaving them unchanged if the patterns do not compile.
+    fn try_update_regex(&mut self) -> Result<(), BotsError> {
+        let patterns = self.compiled_patterns();
+        let (regex, category_regexes) = (
+            Bots::try_to_regex_with_limits(&patterns, &self.size_limits)?,
+            self.category_regexes_for(&patterns),
+        );
+        self.user_agents_regex = regex;
+        self.category_regexes = category_regexes;
+        self.pattern_set = OnceLock::new();
+        self.suffix_regex = OnceLock::new();
+        Ok(())
+    }
+
+    /// Compiles one regular expression per category from the patterns with a category.
+    ///
+    /// The built-in categories are assigned to lowercase default patterns, so in a case-sensitive
+    /// instance those patterns are matched regardless of casing, like the other built-in lists.
+    /// Patterns appended with a category keep the casing of the instance.
+    fn category_regexes_for(&self, patterns: &HashSet<String>) -> HashMap<BotCategory, Regex> {
+        Bots::to_category_regexes(patterns, |pattern| {
+            let category = self.pattern_category(pattern)?;
+            if self.preserve_case && !self.custom_categories.contains_key(pattern) {
+                Some((category, format!("(?i:{})", pattern)))
+            } else {
+                Some((category, pattern.to_string()))
+            }
+        })
+    }
+
+    fn to_category_regexes(
+        regex_entries: &HashSet<String>,
+        categorize: impl Fn(&str) -> Option<(BotCategory, String)>,
+    ) -> HashMap<BotCategory, Regex> {
+        let mut grouped: HashMap<BotCategory, HashSet<String>> = HashMap::new();
+        for entry in regex_entries {
+            if let Some((category, entry)) = categorize(entry) {
+                grouped.entry(category).or_default().insert(entry);
+            }
+        }
+        grouped
+            .into_iter()
+            .map(|(category, entries)| (category, Bots::to_regex(&entries)))
+            .collect()
     }
 
     fn parse_lines(bot_regex_entries: &str) -> HashSet<String> {
-        HashSet::from_iter(
-            bot_regex_entries
-                .lines()
-                .filter(|l| !l.trim().is_empty())
-                .map(ToString::to_string),
-        )
+        HashSet::from_iter(bot_regex_entries.lines().filter_map(Bots::parse_line))
+    }
+
+    /// Compiles each entry on its own, returning an error with the line number of the first invalid one.
+    pub(crate) fn validate_lines(bot_regex_entries: &str) -> Result<(), BotsError> {
+        for (index, line) in bot_regex_entries.lines().enumerate() {
+            if let Some(pattern) = Bots::parse_line(line) {
+                if let Err(source) = Regex::new(&pattern) {
+                    return Err(BotsError::InvalidPattern {
+                        line: Some(index + 1),
+                        pattern,
+                        source,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts an entry to the stored pattern, trimming it and applying its directive. Returns `None`
+    /// for blank lines.
+    pub(crate) fn parse_line(line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() {
+            None
+        } else {
+            Some(Bots::apply_directive(line))
+        }
+    }
+
+    /// Applies the directive of an entry that is already trimmed.
+    pub(crate) fn apply_directive(line: &str) -> String {
+        if let Some(pattern) = line.strip_prefix("!anchor ") {
+            format!("^(?:{})", pattern)
+        } else if let Some(literal) = line.strip_prefix("!exact ") {
+            format!("^{}$", engine::escape(literal))
+        } else {
+            line.to_string()
+        }
     }
 
     fn to_regex(regex_entries: &HashSet<String>) -> Regex {
-        let pattern = regex_entries
+        Bots::try_to_regex(regex_entries).expect("Invalid bot pattern")
+    }
+
+    fn try_to_regex(regex_entries: &HashSet<String>) -> Result<Regex, BotsError> {
+        Bots::try_to_regex_with_limits(regex_entries, &limits::SizeLimits::default())
+    }
+
+    fn try_to_regex_with_limits(
+        regex_entries: &HashSet<String>,
+        size_limits: &limits::SizeLimits,
+    ) -> Result<Regex, BotsError> {
+        let pattern = Bots::to_alternation(regex_entries);
+        size_limits.compile(&pattern).map_err(|source| {
+            // Valid patterns that only fail to compile within the configured limits
+            if !size_limits.is_default() && Regex::new(&pattern).is_ok() {
+                BotsError::SizeLimitExceeded { source }
+            } else {
+                Bots::invalid_pattern(regex_entries, pattern, source)
+            }
+        })
+    }
+
+    /// Joins the entries into one alternation, the source of the combined regular expression.
+    pub(crate) fn to_alternation(regex_entries: &HashSet<String>) -> String {
+        // Sorted so the compiled expression is reproducible, independent of the hash set order
+        let mut entries = regex_entries
             .iter()
-            .cloned()
-            .collect::<Vec<String>>()
-            .join("|");
+            .map(|entry| {
+                // Scope inline flags such as `(?i)` to their own entry instead of every later one
+                if entry.contains("(?") {
+                    Cow::Owned(format!("(?:{})", entry))
+                } else {
+                    Cow::Borrowed(entry.as_str())
+                }
+            })
+            .collect::<Vec<Cow<str>>>();
+        entries.sort_unstable();
+        let pattern = entries.join("|");
 
+        // Without patterns nothing is a bot, including an empty user-agent
         if pattern.is_empty() {
-            return Regex::new("^$").unwrap();
+            r"\b\B".to_string()
+        } else {
+            pattern
+        }
+    }
+
+    /// Compiles each pattern on its own, returning an error for the first invalid one.
+    pub(crate) fn validate_patterns<'a>(
+        patterns: impl IntoIterator<Item = &'a String>,
+    ) -> Result<(), BotsError> {
+        for pattern in patterns {
+            if let Err(source) = Regex::new(pattern) {
+                return Err(BotsError::InvalidPattern {
+                    line: None,
+                    pattern: pattern.clone(),
+                    source,
+                });
+            }
         }
+        Ok(())
+    }
 
-        Regex::new(&pattern).unwrap()
+    /// Finds the entry that made the combined regular expression fail to compile. Falls back to the
+    /// combined pattern if every entry compiles on its own, such as when the size limit is exceeded.
+    fn invalid_pattern(
+        regex_entries: &HashSet<String>,
+        combined: String,
+        source: engine::Error,
+    ) -> BotsError {
+        let mut entries: Vec<&String> = regex_entries.iter().collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .find_map(|pattern| {
+                Regex::new(pattern)
+                    .err()
+                    .map(|source| BotsError::InvalidPattern {
+                        line: None,
+                        pattern: pattern.clone(),
+                        source,
+                    })
+            })
+            .unwrap_or(BotsError::InvalidPattern {
+                line: None,
+                pattern: combined,
+                source,
+            })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Bots;
+    use crate::{BotCategory, Bots, BotsBuilder, BotsError};
 
     static GOOD_BOTS: [&str; 7] = [
         "Googlebot",
@@ -272,11 +1277,25 @@ mod tests {
     fn empty_user_agent_patterns() {
         let empty_user_agent_patterns = "";
         let bots = Bots::new(empty_user_agent_patterns);
-        assert!(bots.is_bot(""));
+        assert!(!bots.is_bot(""));
         assert!(!bots.is_bot("1"));
         assert!(!bots.is_bot("Googlebot"));
     }
 
+    #[test]
+    fn empty_is_bot() {
+        let mut bots = Bots::new("^\\s*$\ngooglebot");
+        for user_agent in ["", "   ", "\t"] {
+            assert!(!bots.is_bot(user_agent), "{:?}", user_agent);
+        }
+        bots.set_empty_is_bot(true);
+        for user_agent in ["", "   ", "\t"] {
+            assert!(bots.is_bot(user_agent), "{:?}", user_agent);
+        }
+        assert!(bots.is_bot("Googlebot"));
+        assert!(!bots.is_bot("Mozilla/5.0"));
+    }
+
     #[test]
     fn single_user_agent_patterns() {
         let single_user_agent_patterns = "me";
@@ -336,4 +1355,434 @@ mod tests {
         assert!(!bots.is_bot("Mozilla/5.0 (Java) outbrain"));
         assert!(!bots.is_bot("Mozilla/5.0 (compatible; Google-Site-Verification/1.0)"));
     }
+
+    #[test]
+    fn remove_checked_patterns() {
+        let mut bots = Bots::default();
+        let report = bots.remove_checked(&["Chrome-Lighthouse", "Chrome Lighthouse"]);
+        assert_eq!(report.removed, vec!["chrome-lighthouse"]);
+        assert_eq!(report.not_found, vec!["chrome lighthouse"]);
+        assert!(!bots.is_bot("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse"));
+
+        let report = bots.remove_checked(&["Chrome-Lighthouse"]);
+        assert!(report.removed.is_empty());
+        assert_eq!(report.not_found, vec!["chrome-lighthouse"]);
+    }
+
+    #[test]
+    fn normalized_user_agent_patterns() {
+        let (bots, report) =
+            Bots::new_normalized("Simplebot\n simplebot\nSIMPLEBOT \n\tSimpleBot\t");
+        assert_eq!(report.collapsed.len(), 3);
+        assert_eq!(bots.user_agent_patterns.len(), 1);
+        assert!(bots.is_bot("Simplebot/1.2"));
+
+        let (bots, report) = Bots::new_normalized(crate::BOT_PATTERNS);
+        assert!(report.collapsed.is_empty());
+        assert_eq!(
+            bots.user_agent_patterns,
+            Bots::default().user_agent_patterns
+        );
+    }
+
+    #[test]
+    fn archivers() {
+        let mut bots = Bots::default();
+        let archivers = [
+            "ia_archiver (+http://www.alexa.com/site/help/webmasters; crawler@alexa.com)",
+            "Mozilla/5.0 (compatible; archive.org_bot +http://www.archive.org/details/archive.org_bot)",
+            "Mozilla/5.0 (compatible; heritrix/3.4.0 +https://www.loc.gov/webarchiving/notice_to_webmasters.html) Firefox/57",
+            "ArchiveTeam ArchiveBot/20170106.02 (wpull 2.0.2)",
+            "InternetArchive/0.8-dev (Nutch; http://lucene.apache.org/nutch/bot.html; nutch-agent@lucene.apache.org)",
+        ];
+        for archiver in archivers {
+            assert!(bots.is_bot(archiver), "Invalid bot: '{}'", archiver);
+            assert!(
+                bots.is_archiver(archiver),
+                "Invalid archiver: '{}'",
+                archiver
+            );
+        }
+        for bot in GOOD_BOTS {
+            assert!(!bots.is_archiver(bot), "Is an archiver: '{}'", bot);
+        }
+        for bot in NOT_BOTS {
+            assert!(!bots.is_archiver(bot), "Is an archiver: '{}'", bot);
+        }
+
+        bots.remove(&["heritrix"]);
+        assert!(!bots.is_archiver(archivers[2]));
+    }
+
+    #[test]
+    fn automation() {
+        let bots = Bots::default();
+        let automation = [
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.6099.28 Safari/537.36",
+            "Mozilla/5.0 (Unknown; Linux x86_64) AppleWebKit/538.1 (KHTML, like Gecko) PhantomJS/2.1.1 Safari/538.1",
+            "selenium/4.15.0 (java windows)",
+            "Playwright/1.40.0 (x64; ubuntu 22.04) node/20.10",
+        ];
+        for user_agent in automation {
+            assert!(bots.is_bot(user_agent), "Invalid bot: '{}'", user_agent);
+            assert!(
+                bots.is_automation(user_agent),
+                "Invalid automation: '{}'",
+                user_agent
+            );
+            assert!(
+                !bots.is_archiver(user_agent),
+                "Is an archiver: '{}'",
+                user_agent
+            );
+        }
+        for bot in GOOD_BOTS {
+            assert!(!bots.is_automation(bot), "Is automation: '{}'", bot);
+        }
+        for bot in NOT_BOTS {
+            assert!(!bots.is_automation(bot), "Is automation: '{}'", bot);
+        }
+    }
+
+    #[test]
+    fn price_scrapers() {
+        let bots = Bots::default();
+        let price_scrapers = [
+            "Mozilla/5.0 (compatible; PriceGrabber/2.0; +http://www.pricegrabber.com)",
+            "Mozilla/5.0 (compatible; Price2Spy/1.0; +https://www.price2spy.com)",
+            "Prisync/1.0 (+https://prisync.com)",
+            "Mozilla/5.0 (compatible; Minderest/1.0)",
+            "PriceRunner/1.0",
+        ];
+        for user_agent in price_scrapers {
+            assert!(bots.is_bot(user_agent), "Invalid bot: '{}'", user_agent);
+            assert!(
+                bots.is_price_scraper(user_agent),
+                "Invalid price scraper: '{}'",
+                user_agent
+            );
+            assert!(
+                !bots.is_automation(user_agent),
+                "Is automation: '{}'",
+                user_agent
+            );
+        }
+        for bot in GOOD_BOTS.iter().chain(NOT_BOTS.iter()) {
+            assert!(!bots.is_price_scraper(bot), "Is price scraper: '{}'", bot);
+        }
+    }
+
+    #[test]
+    fn empty_bots() {
+        let bots = Bots::empty();
+        assert!(bots.user_agent_patterns.is_empty());
+        for bot in GOOD_BOTS {
+            assert!(!bots.is_bot(bot), "Is a bot: '{}'", bot);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "include-default-bots"))]
+    fn default_without_default_bots() {
+        let bots = Bots::default();
+        assert!(bots.is_empty());
+        assert!(!bots.is_bot("Googlebot/2.1"));
+    }
+
+    #[test]
+    fn try_new_reports_invalid_pattern() {
+        assert!(Bots::try_new("googlebot\n^curl/").is_ok());
+        match Bots::try_new("googlebot\n[unclosed\n^curl/") {
+            Err(BotsError::InvalidPattern { line, pattern, .. }) => {
+                assert_eq!(line, Some(2));
+                assert_eq!(pattern, "[unclosed");
+            }
+            other => panic!(
+                "Expected an invalid pattern error, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+
+        let error = Bots::try_new("\n(b\n\n(a").map(|_| ()).unwrap_err();
+        assert!(matches!(
+            error,
+            BotsError::InvalidPattern { line: Some(2), .. }
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("Invalid bot pattern '(b' on line 2: "));
+
+        let mut bots = Bots::empty();
+        assert!(matches!(
+            bots.try_append(&["[unclosed"]),
+            Err(BotsError::InvalidPattern { line: None, .. })
+        ));
+    }
+
+    #[test]
+    fn is_bot_bytes_with_invalid_utf8() {
+        let bots = Bots::new("googlebot\n^curl/\nbad.bot");
+        assert!(bots.is_bot_bytes(b"Googlebot/2.1"));
+        assert!(bots.is_bot_bytes(b"\xc3\x28 Googlebot/2.1 \xff"));
+        assert!(bots.is_bot_bytes(b"curl/\xf0\x28\x8c\x28"));
+        assert!(bots.is_bot_bytes(b"bad\x80bot/1.0"));
+        assert!(!bots.is_bot_bytes(b"Google\xffbot/2.1"));
+        assert!(!bots.is_bot_bytes(b"\xff\xfe\xfd"));
+        assert!(!bots.is_bot_bytes(b""));
+    }
+
+    #[test]
+    fn contains_pattern() {
+        let mut bots = Bots::default();
+        assert!(bots.contains("chrome-lighthouse"));
+        assert!(!bots.contains("chrome-lighthouse/"));
+        bots.remove(&["chrome-lighthouse"]);
+        assert!(!bots.contains("chrome-lighthouse"));
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut bots = Bots::default();
+        bots.remove(&["Chrome-Lighthouse"]);
+        let clone = bots.clone();
+
+        bots.append(&["CustomNewTestB0T"]);
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+        assert!(!clone.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+        assert!(!clone.is_bot(
+            "Mozilla/5.0 (X11; Linux x86_64) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse"
+        ));
+        assert!(clone.is_bot("Googlebot/2.1"));
+        assert_eq!(clone.len() + 1, bots.len());
+    }
+
+    #[test]
+    fn try_append_rolls_back_invalid_patterns() {
+        let mut bots = Bots::new("googlebot");
+        let before = bots.user_agent_patterns.clone();
+        match bots.try_append(&["CustomNewTestB0T", "[unclosed"]) {
+            Err(BotsError::InvalidPattern { pattern, .. }) => assert_eq!(pattern, "[unclosed"),
+            other => panic!("Expected an invalid pattern error, got {:?}", other),
+        }
+        assert_eq!(bots.user_agent_patterns, before);
+        assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+        assert!(bots.is_bot("Googlebot/2.1"));
+
+        bots.try_append(&["CustomNewTestB0T"]).unwrap();
+        assert!(bots.user_agent_patterns.contains("customnewtestb0t"));
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid bot pattern")]
+    fn new_panics_on_invalid_pattern() {
+        Bots::new("googlebot\n(unclosed");
+    }
+
+    #[test]
+    fn pattern_directives() {
+        let bots = Bots::new("!anchor Googlebot\n!exact curl/7.0\nslurp");
+        assert!(bots.is_bot("Googlebot/2.1"));
+        assert!(!bots.is_bot("Mozilla/5.0 (compatible; Googlebot/2.1)"));
+        assert!(bots.is_bot("curl/7.0"));
+        assert!(bots.is_bot("CURL/7.0"));
+        assert!(!bots.is_bot("curl/7.01"));
+        assert!(!bots.is_bot("curl/7x0"));
+        assert!(!bots.is_bot("my curl/7.0"));
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; Yahoo! Slurp)"));
+
+        let bots = Bots::new("!anchor google|bing");
+        assert!(bots.is_bot("bingbot/2.0"));
+        assert!(!bots.is_bot("Mozilla/5.0 (compatible; bingbot/2.0)"));
+    }
+
+    #[test]
+    fn into_matcher() {
+        let is_bot = Bots::default().into_matcher();
+        let bots = Bots::default();
+        for user_agent in GOOD_BOTS.iter().chain(NOT_BOTS.iter()) {
+            assert_eq!(
+                is_bot(user_agent),
+                bots.is_bot(user_agent),
+                "{}",
+                user_agent
+            );
+        }
+
+        let cloned = is_bot.clone();
+        let handle = std::thread::spawn(move || cloned("Googlebot/2.1"));
+        assert!(handle.join().unwrap());
+        assert!(!is_bot(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0"
+        ));
+    }
+
+    #[test]
+    fn remove_patterns_where() {
+        let mut bots = Bots::default();
+        let count = bots.user_agent_patterns.len();
+        let removed = bots.remove_where(|pattern| pattern.contains("google"));
+        assert!(removed.contains(&"google-".to_string()));
+        assert!(removed.iter().all(|pattern| pattern.contains("google")));
+        assert_eq!(bots.user_agent_patterns.len(), count - removed.len());
+
+        assert!(!bots.is_bot("Google-InspectionTool/1.0"));
+        assert!(
+            bots.is_bot("Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)")
+        );
+        for bot in NOT_BOTS {
+            assert!(!bots.is_bot(bot));
+        }
+
+        assert!(bots
+            .remove_where(|pattern| pattern.contains("google"))
+            .is_empty());
+    }
+
+    #[test]
+    fn parsed_fields() {
+        let bots = Bots::default();
+        assert!(bots.is_bot_parsed("Googlebot", None));
+        assert!(bots.is_bot_parsed("Googlebot", Some("Googlebot-Image")));
+        assert!(bots.is_bot_parsed("Other", Some("bingbot")));
+        assert!(!bots.is_bot_parsed("Firefox", None));
+        assert!(!bots.is_bot_parsed("Safari", Some("Mobile Safari")));
+        assert!(!bots.is_bot_parsed("", None));
+    }
+
+    #[test]
+    fn would_be_bot_with_extra_pattern() {
+        let bots = Bots::default();
+        let user_agent = "Mozilla/5.0 (CustomNewTestB0T /1.2)";
+        assert!(!bots.is_bot(user_agent));
+        assert!(bots
+            .would_be_bot_with(user_agent, r"CustomNewTestB0T\s/\d")
+            .unwrap());
+        assert!(!bots.would_be_bot_with(user_agent, "otherb0t").unwrap());
+        assert!(!bots.is_bot(user_agent));
+
+        assert!(bots
+            .would_be_bot_with("Googlebot/2.1", "(unclosed")
+            .unwrap());
+        assert!(bots.would_be_bot_with(user_agent, "(unclosed").is_err());
+    }
+
+    #[test]
+    fn from_iterator() {
+        let bots: Bots = ["^Simplebot", r"Anything\s+Bot", "  ", ""]
+            .into_iter()
+            .collect();
+        assert_eq!(bots.len(), 2);
+        assert!(bots.contains(r"anything\s+bot"));
+        assert!(bots.is_bot("SimpleBot/1.2"));
+        assert!(bots.is_bot("Anything  Bot"));
+        assert!(!bots.is_bot("Googlebot"));
+
+        let bots: Bots = std::iter::empty::<&str>().collect();
+        assert!(bots.is_empty());
+        assert!(!bots.is_bot("Googlebot"));
+    }
+
+    #[test]
+    fn from_vec() {
+        let patterns = vec![
+            "^Simplebot".to_string(),
+            "!exact curl/7.0".to_string(),
+            String::new(),
+        ];
+        let bots = Bots::from(patterns);
+        assert_eq!(bots.len(), 2);
+        assert!(bots.is_bot("simplebot/1.2"));
+        assert!(bots.is_bot("curl/7.0"));
+        assert!(!bots.is_bot("curl/7.01"));
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn clear_then_append() {
+        let mut bots = Bots::default();
+        bots.disable(&["chrome-lighthouse"]);
+        bots.append_case_sensitive(&["^HTTPClient/"]);
+        bots.clear();
+        assert!(bots.is_empty());
+        for user_agent in GOOD_BOTS.iter().chain(&["HTTPClient/1.0"]) {
+            assert!(!bots.is_bot(user_agent), "{}", user_agent);
+        }
+        bots.enable(&["chrome-lighthouse"]);
+        assert!(!bots.is_bot("Chrome-Lighthouse"));
+
+        bots.append(&["^CustomNewTestB0T/"]);
+        assert_eq!(bots.len(), 1);
+        assert!(bots.is_bot("CustomNewTestB0T/1.2"));
+        assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T/1.2)"));
+        for user_agent in GOOD_BOTS {
+            assert!(!bots.is_bot(user_agent), "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn trimmed_duplicates_collapse() {
+        let mut bots = Bots::new("googlebot\nGooglebot \n\t googlebot\r\n^curl/");
+        assert_eq!(bots.len(), 2);
+        assert!(bots.contains("googlebot"));
+
+        bots.append(&["googlebot\n", " GOOGLEBOT", "googlebot"]);
+        assert_eq!(bots.len(), 2);
+        assert!(bots.try_append(&["\tgooglebot "]).is_ok());
+        assert_eq!(bots.len(), 2);
+
+        bots.remove(&["googlebot \n"]);
+        assert_eq!(bots.len(), 1);
+        assert!(!bots.is_bot("Googlebot/2.1"));
+    }
+
+    #[test]
+    fn blank_patterns_are_skipped() {
+        let browser = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        let mut bots = Bots::new("googlebot");
+        bots.append(&["  ", ""]);
+        assert!(bots.try_append(&["\t"]).is_ok());
+        bots.append_raw(&[" "]);
+        bots.append_anchored(&["\n"]);
+        bots.append_with_category(" ", BotCategory::PriceScraper);
+        bots.append_weak(&[("\t", 1.0)]);
+        bots.append_case_sensitive(&["  "]);
+
+        assert_eq!(bots.len(), 1);
+        assert!(!bots.is_bot(browser));
+        assert!(!bots.would_be_bot_with(browser, " ").unwrap());
+
+        let bots = BotsBuilder::new()
+            .patterns("googlebot")
+            .append(&[" ", "\t"])
+            .build()
+            .unwrap();
+        assert_eq!(bots.len(), 1);
+        assert!(!bots.is_bot(browser));
+    }
+
+    #[test]
+    fn deterministic_regex() {
+        let first = Bots::default();
+        let second = Bots::default();
+        assert_eq!(first.as_regex_str(), second.as_regex_str());
+
+        let mut appended = Bots::new("^curl/");
+        appended.append(&["googlebot", "bingbot"]);
+        let reversed: Bots = ["googlebot", "bingbot", "^curl/"].into_iter().collect();
+        assert_eq!(appended.as_regex_str(), "^curl/|bingbot|googlebot");
+        assert_eq!(appended.as_regex_str(), reversed.as_regex_str());
+    }
+
+    #[test]
+    fn equality() {
+        let mut bots = Bots::default();
+        assert_eq!(bots, Bots::default());
+
+        bots.remove(&["googlebot"]);
+        assert_ne!(bots, Bots::default());
+
+        bots.append(&["Googlebot"]);
+        assert_eq!(bots, Bots::default());
+    }
 }
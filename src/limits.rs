@@ -0,0 +1,109 @@
+use crate::engine::{self, Error, Regex};
+use crate::{Bots, BotsError};
+
+/// Size limits, in bytes, applied when compiling the combined regular expression
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct SizeLimits {
+    pub(crate) size: Option<usize>,
+    pub(crate) dfa_size: Option<usize>,
+}
+
+impl SizeLimits {
+    /// Returns `true` if no limit is set, so the engine defaults apply.
+    pub(crate) fn is_default(&self) -> bool {
+        self.size.is_none() && self.dfa_size.is_none()
+    }
+
+    pub(crate) fn compile(&self, pattern: &str) -> Result<Regex, Error> {
+        if self.is_default() {
+            Regex::new(pattern)
+        } else {
+            engine::build_regex(pattern, self.size, self.dfa_size)
+        }
+    }
+}
+
+impl Bots {
+    /// Sets the approximate maximum size, in bytes, of the compiled combined regular expression.
+    ///
+    /// The regular expression engine matches in linear time, so the risk of loading patterns from
+    /// configuration is a large combined expression rather than backtracking. A limit bounds its
+    /// memory: the patterns are recompiled, and if they exceed the limit
+    /// [`BotsError::SizeLimitExceeded`] is returned and the instance is left unchanged. Later calls
+    /// to [`Bots::try_append`] return the same error, while [`Bots::append`] panics. The engine
+    /// default is 10 MiB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Bots, BotsError};
+    ///
+    /// let mut bots = Bots::default();
+    ///
+    /// assert!(matches!(bots.set_size_limit(1024), Err(BotsError::SizeLimitExceeded { .. })));
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    ///
+    /// bots.set_size_limit(4 * 1024 * 1024).unwrap();
+    /// ```
+    pub fn set_size_limit(&mut self, bytes: usize) -> Result<(), BotsError> {
+        let previous = self.size_limits;
+        self.size_limits.size = Some(bytes);
+        self.try_update_regex()
+            .inspect_err(|_| self.size_limits = previous)
+    }
+
+    /// Sets the approximate maximum size, in bytes, of the lazy DFA cache used while matching the
+    /// combined regular expression.
+    ///
+    /// Matching falls back to a slower engine when the cache is too small, so this bounds memory
+    /// per matching thread without rejecting patterns. The `regex-lite` engine has no lazy DFA and
+    /// ignores the limit. The engine default is 2 MiB.
+    pub fn set_dfa_size_limit(&mut self, bytes: usize) -> Result<(), BotsError> {
+        let previous = self.size_limits;
+        self.size_limits.dfa_size = Some(bytes);
+        self.try_update_regex()
+            .inspect_err(|_| self.size_limits = previous)
+    }
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::{Bots, BotsError};
+
+    #[test]
+    fn size_limit_exceeded() {
+        let mut bots = Bots::default();
+        let regex = bots.as_regex_str().to_string();
+
+        let result = bots.set_size_limit(100);
+        assert!(
+            matches!(result, Err(BotsError::SizeLimitExceeded { .. })),
+            "{:?}",
+            result
+        );
+        assert_eq!(bots.as_regex_str(), regex);
+        assert!(bots.is_bot("Googlebot/2.1"));
+
+        let mut bots = Bots::new("googlebot");
+        bots.set_size_limit(10 * 1024).unwrap();
+        bots.set_dfa_size_limit(64 * 1024).unwrap();
+        assert!(bots.is_bot("Googlebot/2.1"));
+
+        let defaults = Bots::default();
+        let patterns: Vec<&str> = defaults
+            .user_agent_patterns
+            .iter()
+            .map(String::as_str)
+            .collect();
+        assert!(matches!(
+            bots.try_append(&patterns),
+            Err(BotsError::SizeLimitExceeded { .. })
+        ));
+        assert_eq!(bots.len(), 1);
+
+        assert!(matches!(
+            bots.try_append(&["(unclosed"]),
+            Err(BotsError::InvalidPattern { .. })
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+//! Debug logging of detected bots with the [log](https://crates.io/crates/log) crate
+
+/// Longest user-agent prefix written to the log, so a huge user-agent does not flood it
+const MAX_LOGGED_USER_AGENT_LEN: usize = 128;
+
+/// Logs a detected bot and the pattern it matched at debug level.
+///
+/// The user-agent comes from the client, so it is written with `{:?}` to escape control characters,
+/// such as a newline that would forge a log line.
+pub(crate) fn log_detection(user_agent: &str, pattern: Option<&str>) {
+    log::debug!(
+        "Bot user-agent {:?} matched pattern '{}'",
+        truncate(user_agent),
+        pattern.unwrap_or("")
+    );
+}
+
+/// Returns the user-agent cut to at most [`MAX_LOGGED_USER_AGENT_LEN`] bytes, on a character boundary.
+fn truncate(user_agent: &str) -> &str {
+    if user_agent.len() <= MAX_LOGGED_USER_AGENT_LEN {
+        return user_agent;
+    }
+    let mut end = MAX_LOGGED_USER_AGENT_LEN;
+    while !user_agent.is_char_boundary(end) {
+        end -= 1;
+    }
+    &user_agent[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{truncate, MAX_LOGGED_USER_AGENT_LEN};
+
+    #[test]
+    fn truncate_on_char_boundary() {
+        assert_eq!(truncate("Googlebot/2.1"), "Googlebot/2.1");
+        let long = "é".repeat(MAX_LOGGED_USER_AGENT_LEN);
+        let truncated = truncate(&long);
+        assert_eq!(truncated.len(), MAX_LOGGED_USER_AGENT_LEN);
+        assert!(long.starts_with(truncated));
+
+        let odd = format!("x{}", long);
+        assert_eq!(truncate(&odd).len(), MAX_LOGGED_USER_AGENT_LEN - 1);
+    }
+}
@@ -0,0 +1,498 @@
+use crate::engine::{Regex, RegexSet};
+use crate::Bots;
+use std::{cmp::Reverse, collections::HashSet};
+
+/// Individually matchable bot patterns, compiled on first use
+#[derive(Debug, Clone)]
+pub(crate) struct PatternSet {
+    patterns: Vec<String>,
+    set: RegexSet,
+}
+
+impl PatternSet {
+    pub(crate) fn new(regex_entries: &HashSet<String>) -> Self {
+        let mut patterns = regex_entries.iter().cloned().collect::<Vec<String>>();
+        patterns.sort();
+        let set = RegexSet::new(&patterns).unwrap();
+        PatternSet { patterns, set }
+    }
+
+    /// Returns the patterns not matching any of the lowercase user-agents, in sorted pattern order.
+    pub(crate) fn unmatched(&self, user_agents: impl Iterator<Item = String>) -> Vec<&str> {
+        let mut matched = vec![false; self.patterns.len()];
+        for user_agent in user_agents {
+            for index in self.set.matches(&user_agent) {
+                matched[index] = true;
+            }
+        }
+        self.patterns
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(pattern, _)| pattern.as_str())
+            .collect()
+    }
+
+    /// Returns the patterns matching the lowercase user-agent, in sorted pattern order.
+    pub(crate) fn matches<'a>(&'a self, user_agent: &str) -> Vec<&'a str> {
+        self.set
+            .matches(user_agent)
+            .into_iter()
+            .map(|index| self.patterns[index].as_str())
+            .collect()
+    }
+}
+
+/// Returns `true` if the pattern is anchored to the start or end of the user-agent.
+pub(crate) fn is_anchored(pattern: &str) -> bool {
+    pattern.starts_with('^') || (pattern.ends_with('$') && !pattern.ends_with("\\$"))
+}
+
+/// Returns the number of literal characters in a pattern, ignoring character classes,
+/// escaped classes such as `\s`, and regular expression operators.
+pub(crate) fn literal_len(pattern: &str) -> usize {
+    let mut count = 0;
+    let mut chars = pattern.chars();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    if !in_class && !escaped.is_ascii_alphanumeric() {
+                        count += 1;
+                    }
+                }
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '.' | '^' | '$' | '*' | '+' | '?' | '(' | ')' | '{' | '}' | '|' => {}
+            _ if in_class => {}
+            _ => count += 1,
+        }
+    }
+    count
+}
+
+/// Sort key ranking more specific patterns first: anchored, then more literal characters, then
+/// longer patterns, then alphabetically for a deterministic order.
+pub(crate) fn specificity(pattern: &str) -> (Reverse<bool>, Reverse<usize>, Reverse<usize>, &str) {
+    (
+        Reverse(is_anchored(pattern)),
+        Reverse(literal_len(pattern)),
+        Reverse(pattern.len()),
+        pattern,
+    )
+}
+
+/// Unrelated user-agents that no single bot pattern should match all of
+const CATCH_ALL_PROBES: &[&str] = &[
+    "mozilla/5.0 (windows nt 10.0; win64; x64) applewebkit/537.36 (khtml, like gecko) chrome/120.0.0.0 safari/537.36",
+    "opera/9.60 (windows nt 6.0; u; en) presto/2.1.1",
+    "x",
+    "7",
+    " ",
+    "zq#k!~",
+];
+
+impl Bots {
+    pub(crate) fn pattern_set(&self) -> &PatternSet {
+        self.pattern_set
+            .get_or_init(|| PatternSet::new(&self.compiled_patterns()))
+    }
+
+    /// Returns every pattern matching the user-agent, such as to debug a false positive.
+    ///
+    /// The order is pattern-index order, and patterns are indexed alphabetically when the set is
+    /// compiled. Use [`Bots::ranked_matches`] to order the patterns by specificity instead.
+    ///
+    /// Individual patterns are compiled into a separate set of regular expressions the first time a
+    /// per-pattern method is called, and again after the patterns change. Matching each pattern
+    /// is slower than [`Bots::is_bot`], so prefer `is_bot` when only a verdict is needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("bot\ngooglebot\nbingbot");
+    ///
+    /// assert_eq!(bots.matching_patterns("Googlebot/2.1"), vec!["bot", "googlebot"]);
+    /// assert!(bots.matching_patterns("Mozilla/5.0").is_empty());
+    /// ```
+    pub fn matching_patterns(&self, user_agent: &str) -> Vec<&str> {
+        self.pattern_set()
+            .matches(&self.normalize_user_agent(user_agent))
+    }
+
+    /// Returns the most specific pattern matching the user-agent, useful as a single label.
+    ///
+    /// Patterns anchored with `^` or `$` are the most specific, followed by patterns with more
+    /// literal characters, then longer patterns. Remaining ties are broken alphabetically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("bot\ngooglebot\n^googlebot-image/");
+    ///
+    /// assert_eq!(bots.best_match("Googlebot/2.1"), Some("googlebot"));
+    /// assert_eq!(bots.best_match("Googlebot-Image/1.0"), Some("^googlebot-image/"));
+    /// assert_eq!(bots.best_match("Mozilla/5.0"), None);
+    /// ```
+    pub fn best_match(&self, user_agent: &str) -> Option<&str> {
+        self.matching_patterns(user_agent)
+            .into_iter()
+            .min_by_key(|pattern| specificity(pattern))
+    }
+
+    /// Returns the stored pattern that detects the user-agent as a bot, such as to log it or to route
+    /// good bots differently, or `None` if it is not a bot.
+    ///
+    /// When several patterns match, the most specific one is returned, as in [`Bots::best_match`].
+    /// Weak patterns are only returned when their combined weight detects the user-agent, and
+    /// case-sensitive patterns are included. Allowlisted user-agents and excluded categories return
+    /// `None`, as they do for [`Bots::is_bot`], but the detection hook is not called. The combined
+    /// regular expression used by `is_bot` cannot tell which alternative matched, so each pattern is
+    /// matched on its own, which is much slower than `is_bot`. Prefer calling it only after `is_bot`
+    /// returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("googlebot\n^curl/");
+    ///
+    /// assert_eq!(bots.which_bot("Googlebot/2.1"), Some("googlebot"));
+    /// assert_eq!(bots.which_bot("Mozilla/5.0"), None);
+    /// ```
+    pub fn which_bot(&self, user_agent: &str) -> Option<&str> {
+        let normalized = self.normalize_user_agent(user_agent);
+        if !self.layers.is_empty() && !self.resolve_layers(user_agent, &normalized) {
+            return None;
+        }
+        let pattern = self.detected_pattern(user_agent, &normalized);
+        #[cfg(feature = "log")]
+        if pattern.is_some() {
+            crate::logging::log_detection(user_agent, pattern);
+        }
+        pattern
+    }
+
+    /// Returns the most specific pattern of any kind that matches, without checking layers: a regular
+    /// pattern, a case-sensitive pattern, or a weak pattern when the weak patterns detect it.
+    pub(crate) fn detected_pattern(&self, user_agent: &str, normalized: &str) -> Option<&str> {
+        let mut patterns = self.pattern_set().matches(normalized);
+        patterns.extend(self.case_sensitive.matches(user_agent));
+        if self.weak.is_match(normalized) {
+            patterns.extend(self.weak.matches(normalized));
+        }
+        patterns
+            .into_iter()
+            .min_by_key(|pattern| specificity(pattern))
+    }
+
+    /// Returns all patterns matching the user-agent, ranked from the most to the least specific.
+    ///
+    /// The ranking is the same as [`Bots::best_match`], so the first pattern is the best label and the
+    /// rest give context: anchored patterns, then patterns with more literal characters, then longer
+    /// patterns, with remaining ties broken alphabetically. The order is deterministic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("bot\ngooglebot\n^googlebot-image/");
+    ///
+    /// assert_eq!(
+    ///     bots.ranked_matches("Googlebot-Image/1.0"),
+    ///     vec!["^googlebot-image/", "googlebot", "bot"]
+    /// );
+    /// ```
+    pub fn ranked_matches(&self, user_agent: &str) -> Vec<&str> {
+        let mut matches = self.matching_patterns(user_agent);
+        matches.sort_by_key(|pattern| specificity(pattern));
+        matches
+    }
+
+    /// Returns the shortest text of the user-agent matched by a pattern, useful for writing a precise
+    /// exclusion for a false positive.
+    ///
+    /// Each matching pattern is compiled on its own to find its first match, and the shortest match is
+    /// returned, preferring the earliest one on ties. The text is taken from the user-agent as given,
+    /// unless normalizers are set, in which case it is taken from the normalized user-agent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("[a-z]*lighthouse\nbot");
+    ///
+    /// assert_eq!(bots.match_trigger("Mozilla/5.0 Chrome-Lighthouse").as_deref(), Some("Lighthouse"));
+    /// assert_eq!(bots.match_trigger("Mozilla/5.0"), None);
+    /// ```
+    pub fn match_trigger(&self, user_agent: &str) -> Option<String> {
+        let normalized = self.normalize_user_agent(user_agent);
+        let text = if self.normalizers.is_empty() {
+            user_agent
+        } else {
+            normalized.as_str()
+        };
+        self.pattern_set()
+            .matches(&normalized)
+            .into_iter()
+            .filter_map(|pattern| Regex::new(pattern).ok()?.find(&normalized))
+            .min_by_key(|m| (m.len(), m.start()))
+            .map(|m| text[m.range()].to_string())
+    }
+
+    /// Returns `true` if a pattern matches arbitrary input, such as `.*` or an empty pattern, which
+    /// would make every user-agent a bot.
+    ///
+    /// Each pattern is matched against a set of unrelated probe strings, and a pattern matching all of
+    /// them is a catch-all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// assert!(!bots.has_catch_all());
+    ///
+    /// bots.append(&[".*"]);
+    /// assert!(bots.has_catch_all());
+    /// ```
+    pub fn has_catch_all(&self) -> bool {
+        let set = self.pattern_set();
+        let mut candidates = set.matches(CATCH_ALL_PROBES[0]);
+        for probe in &CATCH_ALL_PROBES[1..] {
+            let matches = set.matches(probe);
+            candidates.retain(|pattern| matches.contains(pattern));
+        }
+        !candidates.is_empty()
+    }
+
+    /// Returns `true` if a pattern matches at the very end of the user-agent, ignoring trailing whitespace.
+    ///
+    /// This identifies the common shape of an instrumented browser: a regular browser user-agent
+    /// with a bot or tool token appended, such as `Chrome-Lighthouse`. Patterns that only match
+    /// earlier in the user-agent are ignored. The end anchored expression is compiled the first
+    /// time this is called, and again after the patterns change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_bot_suffix("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse"));
+    /// assert!(!bots.is_bot_suffix("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// ```
+    pub fn is_bot_suffix(&self, user_agent: &str) -> bool {
+        self.suffix_regex
+            .get_or_init(|| {
+                let alternation = Bots::to_alternation(&self.compiled_patterns());
+                Regex::new(&format!(r"(?:{})\s*$", alternation)).unwrap()
+            })
+            .is_match(&self.normalize_user_agent(user_agent))
+    }
+
+    /// Returns the patterns that match none of the user-agents in the corpus, sorted alphabetically.
+    ///
+    /// Run this against a representative production log to find patterns for bots that never
+    /// visit, which are candidates for removal to shrink the combined regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("googlebot\nbingbot\nduckduckbot");
+    /// let corpus = ["Googlebot/2.1", "Mozilla/5.0 (compatible; bingbot/2.0)"];
+    ///
+    /// assert_eq!(bots.dead_patterns(&corpus), vec!["duckduckbot"]);
+    /// ```
+    pub fn dead_patterns(&self, corpus: &[&str]) -> Vec<&str> {
+        self.pattern_set().unmatched(
+            corpus
+                .iter()
+                .map(|user_agent| self.normalize_user_agent(user_agent)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::literal_len;
+    use crate::Bots;
+
+    #[test]
+    fn literal_lengths() {
+        assert_eq!(literal_len("googlebot"), 9);
+        assert_eq!(literal_len("^googlebot"), 9);
+        assert_eq!(literal_len(r"archive\.org_bot"), 15);
+        assert_eq!(literal_len(r"[ ]+bot"), 3);
+        assert_eq!(literal_len(r"fdm[\s/]\d"), 3);
+    }
+
+    #[test]
+    fn best_match_prefers_specific_pattern() {
+        let bots = Bots::new("[a-z]+bot\ngooglebot\nbot");
+        let user_agent = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        assert_eq!(
+            bots.matching_patterns(user_agent),
+            vec!["[a-z]+bot", "bot", "googlebot"]
+        );
+        assert_eq!(bots.best_match(user_agent), Some("googlebot"));
+    }
+
+    #[test]
+    fn ranked_matches_by_specificity() {
+        let bots = Bots::new("[a-z]+bot\ngooglebot\nbot\n^mozilla/\ngoogle");
+        let user_agent = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        assert_eq!(
+            bots.ranked_matches(user_agent),
+            vec!["^mozilla/", "googlebot", "google", "[a-z]+bot", "bot"]
+        );
+        assert_eq!(
+            bots.ranked_matches(user_agent).first().copied(),
+            bots.best_match(user_agent)
+        );
+        assert!(bots.ranked_matches("curl/7.64.1").is_empty());
+    }
+
+    #[test]
+    fn which_bot_returns_matching_pattern() {
+        let bots = Bots::new("bot\ngooglebot\nhttp[s]?://\nbingbot");
+        let user_agent = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        assert_eq!(bots.which_bot(user_agent), Some("googlebot"));
+        assert_eq!(bots.which_bot("Googlebot/2.1"), Some("googlebot"));
+        assert_eq!(
+            bots.which_bot("Mozilla/5.0 (Windows NT 10.0; Win64; x64)"),
+            None
+        );
+
+        let bots = Bots::builder()
+            .patterns("googlebot\nbot")
+            .allow(&["googlebot-image"])
+            .build()
+            .unwrap();
+        assert_eq!(bots.which_bot("Googlebot/2.1"), Some("googlebot"));
+        assert_eq!(bots.which_bot("Googlebot-Image/1.0"), None);
+    }
+
+    #[test]
+    fn which_bot_with_weak_and_case_sensitive_patterns() {
+        let mut bots = Bots::new("googlebot");
+        bots.append_weak(&[("spider", 0.5), ("crawl", 0.5)]);
+        bots.append_case_sensitive(&["^KoalaClient/"]);
+
+        assert_eq!(bots.which_bot("NiceSpider/2.0"), None);
+        assert_eq!(bots.which_bot("NiceSpider/2.0 (crawl)"), Some("spider"));
+        assert_eq!(bots.which_bot("KoalaClient/1.0"), Some("^KoalaClient/"));
+        assert_eq!(bots.which_bot("koalaclient/1.0"), None);
+        for user_agent in ["NiceSpider/2.0 (crawl)", "KoalaClient/1.0", "Googlebot/2.1"] {
+            assert_eq!(
+                bots.which_bot(user_agent).is_some(),
+                bots.is_bot(user_agent)
+            );
+        }
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn matches_multiple_default_patterns() {
+        let bots = Bots::default();
+        let user_agent = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        let matches = bots.matching_patterns(user_agent);
+        assert!(matches.contains(&"googlebot"), "{:?}", matches);
+        assert!(matches.contains(&"http[s]?://"), "{:?}", matches);
+        assert!(matches.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(bots
+            .matching_patterns("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0")
+            .is_empty());
+    }
+
+    #[test]
+    fn best_match_after_append() {
+        let mut bots = Bots::new("bot");
+        assert_eq!(bots.best_match("Googlebot"), Some("bot"));
+        bots.append(&["^Googlebot"]);
+        assert_eq!(bots.best_match("Googlebot"), Some("^googlebot"));
+        assert_eq!(bots.best_match("Mozilla/5.0 (Googlebot)"), Some("bot"));
+    }
+
+    #[test]
+    fn dead_patterns_in_corpus() {
+        let bots = Bots::new("^googlebot\nbingbot\nchrome-lighthouse\nslurp");
+        let corpus = [
+            "Googlebot/2.1 (+http://www.google.com/bot.html)",
+            "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)",
+            "Mozilla/5.0 (compatible; Yahoo! Slurp; http://help.yahoo.com/help/us/ysearch/slurp)",
+            "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36",
+        ];
+        assert_eq!(bots.dead_patterns(&corpus), vec!["chrome-lighthouse"]);
+        assert_eq!(bots.dead_patterns(&[]).len(), 4);
+    }
+
+    #[test]
+    fn suffix_matches() {
+        let bots = Bots::default();
+        assert!(bots.is_bot_suffix("Mozilla/5.0 (Linux; Android 7.0; Moto G (4)) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/84.0.4143.7 Mobile Safari/537.36 Chrome-Lighthouse"));
+        assert!(bots.is_bot_suffix(
+            "Mozilla/5.0 (X11; Linux x86_64) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse  "
+        ));
+        assert!(!bots.is_bot_suffix("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36"));
+        assert!(!bots.is_bot_suffix(
+            "Mozilla/5.0 (compatible; Bingbot/2.0; +http://www.bing.com/bingbot.htm)"
+        ));
+        assert!(!Bots::empty().is_bot_suffix(""));
+
+        // Inline flags stay scoped to their own pattern
+        let bots = Bots::new_case_sensitive("(?i)acmebot\nHTTPClient");
+        assert!(bots.is_bot_suffix("Mozilla/5.0 ACMEBOT"));
+        assert!(bots.is_bot_suffix("Mozilla/5.0 HTTPClient"));
+        assert!(!bots.is_bot_suffix("Mozilla/5.0 httpclient"));
+    }
+
+    #[test]
+    fn false_positive_trigger() {
+        let bots = Bots::new("^[a-z.0-9/ \\-_]*bot\nbingpreview/\ncubot");
+        let browser = "Mozilla/5.0 (Linux; Android 10; CUBOT X30) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Mobile Safari/537.36";
+        assert!(bots.is_bot(browser));
+        assert_eq!(bots.match_trigger(browser).as_deref(), Some("CUBOT"));
+        assert_eq!(
+            bots.match_trigger("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Chrome/120.0"),
+            None
+        );
+
+        let mut bots = Bots::new("^vendorbot/#");
+        bots.add_normalizer(r"\d+", "#").unwrap();
+        assert_eq!(
+            bots.match_trigger("VendorBot/12.3").as_deref(),
+            Some("vendorbot/#")
+        );
+    }
+
+    #[test]
+    fn catch_all_patterns() {
+        let mut bots = Bots::new("googlebot\n^curl");
+        assert!(!bots.has_catch_all());
+        bots.append(&[".*"]);
+        assert!(bots.has_catch_all());
+
+        for pattern in ["^", ".", "(?:)|bot", "(?s)."] {
+            assert!(Bots::new(pattern).has_catch_all(), "{}", pattern);
+        }
+        let mut bots = Bots::empty();
+        bots.append(&[""]);
+        assert!(!bots.has_catch_all());
+        bots.append(&["(?:)"]);
+        assert!(bots.has_catch_all());
+    }
+}
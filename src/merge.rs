@@ -0,0 +1,95 @@
+use crate::Bots;
+use std::collections::BTreeSet;
+
+/// Merges newline delimited pattern lists into a single sorted list without duplicates.
+///
+/// Each line is trimmed and converted to lowercase, and blank lines and comment lines starting with `#`
+/// are skipped. The result uses the same canonical form as
+/// [bot_regex_patterns.txt](https://github.com/BryanMorgan/isbot/blob/main/src/bot_regex_patterns.txt),
+/// so it can be written back with one pattern per line.
+///
+/// # Example
+///
+/// ```
+/// let merged = isbot::merge_sources(&["# first list\nGooglebot\ncurl/", "  curl/\nBingbot  \n"]);
+///
+/// assert_eq!(merged, vec!["bingbot", "curl/", "googlebot"]);
+/// ```
+pub fn merge_sources(sources: &[&str]) -> Vec<String> {
+    sources
+        .iter()
+        .flat_map(|source| source.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_ascii_lowercase)
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+impl Bots {
+    /// Adds all bot user-agent patterns of `other`, such as to combine an organization wide list with
+    /// an application specific one, and recompiles the regular expression once.
+    ///
+    /// Patterns are added as stored in `other`, including the patterns of its shared base, see
+    /// [`Bots::with_shared_base`], and patterns already loaded are not duplicated. Disabled, weak, and
+    /// case-sensitive patterns and settings such as normalizers are not merged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("googlebot");
+    /// bots.merge(&Bots::new("googlebot\n^curl/"));
+    ///
+    /// assert_eq!(bots.len(), 2);
+    /// assert!(bots.is_bot("curl/7.64.1"));
+    /// ```
+    pub fn merge(&mut self, other: &Bots) {
+        self.user_agent_patterns
+            .extend(other.compiled_patterns().iter().cloned());
+        self.update_regex()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_sources;
+
+    #[test]
+    fn merge_overlapping_sources() {
+        let merged = merge_sources(&[
+            "^curl\nGooglebot\n# comment\n\nslurp",
+            "SLURP\n  ^curl  \nbingbot\n",
+            "",
+        ]);
+        assert_eq!(merged, vec!["^curl", "bingbot", "googlebot", "slurp"]);
+        assert!(merged.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn default_patterns_are_canonical() {
+        let merged = merge_sources(&[crate::BOT_PATTERNS, crate::BOT_PATTERNS]);
+        assert_eq!(merged, crate::BOT_PATTERNS.lines().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "include-default-bots")]
+    #[test]
+    fn merge_into_default() {
+        use crate::Bots;
+
+        let mut bots = Bots::default();
+        let default_len = bots.len();
+        let custom = Bots::new(r"CustomNewTestB0T\s/\d\.\d");
+        assert!(!bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+
+        bots.merge(&custom);
+        bots.merge(&custom);
+        assert_eq!(bots.len(), default_len + 1);
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+        assert!(bots.is_bot("Googlebot/2.1"));
+        assert!(!bots.is_bot("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::Bots;
+
+impl Bots {
+    /// Returns `true` if any user-agent in a merged header value is a known bot.
+    ///
+    /// Some proxies join repeated `User-Agent` headers into a single value separated by commas or
+    /// newlines, which hides a bot behind a browser user-agent and defeats anchored patterns. The value
+    /// is split on those separators and each trimmed segment is matched with [`Bots::is_bot`]. Commas
+    /// inside parenthesized comments, such as `(KHTML, like Gecko)`, do not split the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new("^curl/");
+    ///
+    /// assert!(!bots.is_bot("Mozilla/5.0 (KHTML, like Gecko) Chrome/120.0, curl/8.4.0"));
+    /// assert!(bots.is_bot_multivalue("Mozilla/5.0 (KHTML, like Gecko) Chrome/120.0, curl/8.4.0"));
+    /// assert!(!bots.is_bot_multivalue("Mozilla/5.0 (KHTML, like Gecko) Chrome/120.0"));
+    /// ```
+    pub fn is_bot_multivalue(&self, value: &str) -> bool {
+        split_values(value).any(|user_agent| self.is_bot(user_agent))
+    }
+}
+
+/// Splits a header value on commas and newlines outside of parentheses, skipping empty segments.
+fn split_values(value: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0usize;
+    value
+        .split(move |c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            depth == 0 && matches!(c, ',' | '\n' | '\r')
+        })
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_values;
+    use crate::Bots;
+
+    #[test]
+    fn split_merged_values() {
+        let values: Vec<_> = split_values(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko),\r\n curl/8.4.0,,",
+        )
+        .collect();
+        assert_eq!(
+            values,
+            [
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko)",
+                "curl/8.4.0"
+            ]
+        );
+    }
+
+    #[test]
+    fn multivalue_bot() {
+        let bots = Bots::default();
+        let browser = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36";
+        assert!(!bots.is_bot_multivalue(browser));
+        assert!(bots.is_bot_multivalue(&format!("{}, Googlebot/2.1", browser)));
+        assert!(bots.is_bot_multivalue(&format!("{}\nGooglebot/2.1", browser)));
+        assert!(!bots.is_bot_multivalue(&format!("{}, {}", browser, browser)));
+        assert!(!bots.is_bot_multivalue(""));
+    }
+}
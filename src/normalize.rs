@@ -0,0 +1,256 @@
+use crate::engine::Regex;
+use crate::{Bots, BotsError};
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+/// Longest user-agent lowercased on the stack rather than the heap when no normalizers are set
+const STACK_USER_AGENT_LEN: usize = 256;
+
+/// Largest per-thread buffer kept after matching, so a single huge user-agent does not pin its memory
+const MAX_REUSED_BUFFER_CAPACITY: usize = 8 * 1024;
+
+thread_local! {
+    /// Buffer reused for user-agents that do not fit on the stack or need normalizing
+    static USER_AGENT_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Replacement applied to a lowercase user-agent before matching
+#[derive(Debug, Clone)]
+pub(crate) struct Normalizer {
+    regex: Regex,
+    replacement: String,
+}
+
+impl Normalizer {
+    pub(crate) fn new(regex: Regex, replacement: &str) -> Self {
+        Normalizer {
+            regex,
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+impl Bots {
+    /// Adds a regular expression replacement applied to user-agents before matching.
+    ///
+    /// Normalizers run in the order they were added, against the lowercase user-agent, and every match
+    /// is replaced with `replacement`, which may refer to capture groups such as `$1`. The pattern is
+    /// trimmed like the bot patterns, so write a leading or trailing space as `[ ]`, and the pattern
+    /// and replacement are converted to lowercase. No normalizers are applied by default.
+    ///
+    /// A normalizer lets one pattern cover user-agents that differ only in noise, such as bots that
+    /// randomize their version number. Broad normalizers also change browser user-agents, so check
+    /// that patterns written against the normalized text do not cause false positives.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::new("^vendorbot/n.n.n$");
+    /// assert!(!bots.is_bot("VendorBot/1.2.3"));
+    ///
+    /// bots.add_normalizer(r"\d+", "n").unwrap();
+    /// assert!(bots.is_bot("VendorBot/1.2.3"));
+    /// assert!(bots.is_bot("VendorBot/9.10.11"));
+    /// ```
+    pub fn add_normalizer(&mut self, pattern: &str, replacement: &str) -> Result<(), BotsError> {
+        let pattern = Bots::lowercase_pattern(pattern);
+        let regex = Regex::new(&pattern).map_err(|source| BotsError::InvalidPattern {
+            line: None,
+            pattern,
+            source,
+        })?;
+        self.normalizers
+            .push(Normalizer::new(regex, &replacement.to_ascii_lowercase()));
+        Ok(())
+    }
+
+    /// Returns the lowercase user-agent with all normalizers applied, as used for matching.
+    pub(crate) fn normalize_user_agent(&self, user_agent: &str) -> String {
+        let mut normalized = String::with_capacity(user_agent.len());
+        self.normalize_user_agent_into(user_agent, &mut normalized);
+        normalized
+    }
+
+    /// Returns a normalized user-agent in lowercase for matching the built-in lists, such as the
+    /// device hints and browser tokens, which are lowercase even in a case-sensitive instance.
+    pub(crate) fn lowercase_normalized<'a>(&self, normalized: &'a str) -> Cow<'a, str> {
+        if self.preserve_case {
+            Cow::Owned(normalized.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(normalized)
+        }
+    }
+
+    /// Returns `true` if the user-agent, or a trimmed or whitespace-collapsed variant of it, is a
+    /// known bot.
+    ///
+    /// This is more forgiving than [`Bots::is_bot`] for messy input such as log data, where padding or
+    /// repeated whitespace can keep an anchored pattern or a pattern containing a space from matching.
+    /// Matching is already case-insensitive, so casing variants are not checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(!bots.is_bot("  curl/7.64.1"));
+    /// assert!(bots.is_bot_robust("  curl/7.64.1"));
+    /// ```
+    pub fn is_bot_robust(&self, user_agent: &str) -> bool {
+        if self.is_bot(user_agent) {
+            return true;
+        }
+        let trimmed = user_agent.trim();
+        if trimmed.len() != user_agent.len() && self.is_bot(trimmed) {
+            return true;
+        }
+        let collapsed = trimmed.split_whitespace().collect::<Vec<&str>>().join(" ");
+        collapsed != trimmed && self.is_bot(&collapsed)
+    }
+
+    /// Calls `f` with the normalized user-agent, without a heap allocation for typical user-agents.
+    ///
+    /// User-agents up to [`STACK_USER_AGENT_LEN`] bytes are lowercased into a stack buffer when no
+    /// normalizers are set, which keeps the common no-match case for browser traffic allocation free.
+    /// Longer user-agents are lowercased into a per-thread buffer that is reused across calls, so only
+    /// growing the buffer and replacing text with a normalizer allocate.
+    pub(crate) fn with_normalized_user_agent<R>(
+        &self,
+        user_agent: &str,
+        f: impl FnOnce(&str) -> R,
+    ) -> R {
+        if self.normalizers.is_empty() && user_agent.len() <= STACK_USER_AGENT_LEN {
+            let mut buffer = [0u8; STACK_USER_AGENT_LEN];
+            let lowercase = &mut buffer[..user_agent.len()];
+            lowercase.copy_from_slice(user_agent.as_bytes());
+            if !self.preserve_case {
+                lowercase.make_ascii_lowercase();
+            }
+            // ASCII lowercasing only changes ASCII bytes, so the buffer is still valid UTF-8
+            f(std::str::from_utf8(lowercase).expect("Lowercase user-agent is not UTF-8"))
+        } else {
+            USER_AGENT_BUFFER.with(|buffer| match buffer.try_borrow_mut() {
+                Ok(mut buffer) => {
+                    self.normalize_user_agent_into(user_agent, &mut buffer);
+                    let result = f(&buffer);
+                    if buffer.capacity() > MAX_REUSED_BUFFER_CAPACITY {
+                        *buffer = String::new();
+                    }
+                    result
+                }
+                // Called again from within `f`, so the buffer is already in use
+                Err(_) => f(&self.normalize_user_agent(user_agent)),
+            })
+        }
+    }
+
+    /// Writes the normalized user-agent into `buffer`, replacing its contents, so the allocation can be reused.
+    pub(crate) fn normalize_user_agent_into(&self, user_agent: &str, buffer: &mut String) {
+        buffer.clear();
+        buffer.push_str(user_agent);
+        if !self.preserve_case {
+            buffer.make_ascii_lowercase();
+        }
+        for normalizer in &self.normalizers {
+            let replaced = normalizer
+                .regex
+                .replace_all(buffer, normalizer.replacement.as_str());
+            if let Cow::Owned(replaced) = replaced {
+                *buffer = replaced;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, BotsBuilder, BotsError};
+
+    #[test]
+    fn strip_noise() {
+        let builder = BotsBuilder::new().patterns(r"^scraperbot/\d+$");
+        let padded = "ScraperBot/2 (en-US,en;q=0.9)";
+
+        let bots = builder.clone().build().unwrap();
+        assert!(!bots.is_bot(padded));
+
+        let bots = builder
+            .strip_noise(&[r"\s*\([a-z]{2}-[a-z]{2}(,[a-z]{2}(;q=[\d.]+)?)*\)"])
+            .build()
+            .unwrap();
+        assert!(bots.is_bot(padded));
+        assert!(bots.is_bot("ScraperBot/2"));
+        assert!(!bots.is_bot("ScraperBot/2 (compatible)"));
+    }
+
+    #[test]
+    fn invalid_noise_pattern() {
+        let result = BotsBuilder::new().strip_noise(&["(unclosed"]).build();
+        assert!(matches!(
+            result,
+            Err(BotsError::InvalidPattern { pattern, .. }) if pattern == "(unclosed"
+        ));
+    }
+
+    #[test]
+    fn version_normalizer() {
+        let mut bots = Bots::new(r"^vendorbot/#\.#\.# \(\+https://vendor\.example\)$");
+        let user_agents = [
+            "VendorBot/1.2.3 (+https://vendor.example)",
+            "VendorBot/9.9.9 (+https://vendor.example)",
+        ];
+        for user_agent in user_agents {
+            assert!(!bots.is_bot(user_agent));
+        }
+
+        bots.add_normalizer(r"\d+", "#").unwrap();
+        for user_agent in user_agents {
+            assert!(bots.is_bot(user_agent));
+        }
+        assert!(!bots.is_bot("VendorBot/1.2 (+https://vendor.example)"));
+    }
+
+    #[test]
+    fn invalid_normalizer() {
+        let mut bots = Bots::empty();
+        assert!(matches!(
+            bots.add_normalizer("[unclosed", ""),
+            Err(BotsError::InvalidPattern { pattern, .. }) if pattern == "[unclosed"
+        ));
+    }
+
+    #[test]
+    fn stack_buffer_boundary() {
+        let bots = Bots::new("^vendorbot/|bot$");
+        for len in [
+            super::STACK_USER_AGENT_LEN - 1,
+            super::STACK_USER_AGENT_LEN,
+            super::STACK_USER_AGENT_LEN + 1,
+        ] {
+            let prefix = "VendorBot/";
+            let user_agent = format!("{}{}", prefix, "X".repeat(len - prefix.len()));
+            assert_eq!(user_agent.len(), len);
+            assert!(bots.is_bot(&user_agent), "{}", len);
+
+            let suffix = "ÉBOT";
+            let user_agent = format!("{}{}", "é".repeat((len - suffix.len()) / 2), suffix);
+            assert!(bots.is_bot(&user_agent), "{}", len);
+            assert!(!bots.is_bot(&user_agent.replace("BOT", "B0T")), "{}", len);
+        }
+    }
+
+    #[test]
+    fn robust_whitespace_variants() {
+        let bots = Bots::new("^curl/\nfancy b0t/");
+        for user_agent in ["\tcurl/7.0 ", "Mozilla/5.0 (Fancy  \t B0T/1.0)"] {
+            assert!(!bots.is_bot(user_agent), "{}", user_agent);
+            assert!(bots.is_bot_robust(user_agent), "{}", user_agent);
+        }
+        assert!(bots.is_bot_robust("curl/7.0"));
+        assert!(!bots.is_bot_robust("  Mozilla/5.0  (Windows NT 10.0)"));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::engine::Regex;
+use crate::Bots;
+
+impl Bots {
+    /// Appends patterns written for PCRE, such as nginx or Apache configurations, or for JavaScript,
+    /// translating common constructs the regular expression engine does not support.
+    ///
+    /// Translation is best-effort and only covers constructs with the same result for matching:
+    ///
+    /// - `/pattern/flags` delimiters are removed
+    /// - `(?<name>...)` named groups become `(?P<name>...)`
+    /// - a positive lookbehind at the start, `(?<=...)`, and a positive lookahead at the end,
+    ///   `(?=...)`, become plain groups, since they only widen the matched text
+    /// - atomic groups `(?>...)` become plain groups, which can make a pattern match more often
+    /// - `\/` becomes `/` and `\h` becomes `[ \t]`
+    ///
+    /// Patterns are converted to lowercase. Translated patterns are appended even if others fail, and
+    /// patterns that still do not compile, such as those using negative lookarounds or backreferences,
+    /// are returned with the compile error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::empty();
+    /// let errors = bots
+    ///     .append_pcre_compat(&["/^(?<name>fancyb0t)\\/\\d+/i", "^(?!mozilla)spidey"])
+    ///     .unwrap_err();
+    ///
+    /// assert!(bots.is_bot("FancyB0T/2"));
+    /// assert_eq!(errors[0].0, "^(?!mozilla)spidey");
+    /// ```
+    pub fn append_pcre_compat(&mut self, patterns: &[&str]) -> Result<(), Vec<(String, String)>> {
+        let mut errors = Vec::new();
+        for pattern in patterns {
+            let translated = translate(&pattern.to_ascii_lowercase());
+            if translated.trim().is_empty() {
+                continue;
+            }
+            match Regex::new(&translated) {
+                Ok(_) => {
+                    self.user_agent_patterns.insert(translated);
+                }
+                Err(e) => errors.push((pattern.to_string(), e.to_string())),
+            }
+        }
+        self.update_regex();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Translates the PCRE constructs with an equivalent for matching, leaving anything else unchanged.
+fn translate(pattern: &str) -> String {
+    let mut pattern = name_groups(strip_delimiters(pattern))
+        .replace("(?>", "(?:")
+        .replace(r"\/", "/")
+        .replace(r"\h", r"[ \t]");
+
+    if pattern.starts_with("(?<=") && closing_paren(&pattern, 0).is_some() {
+        pattern.replace_range(..4, "(?:");
+    }
+    if let Some(start) = pattern.rfind("(?=") {
+        if closing_paren(&pattern, start) == Some(pattern.len() - 1) {
+            pattern.replace_range(start..start + 3, "(?:");
+        }
+    }
+    pattern
+}
+
+/// Removes `/pattern/flags` delimiters from a JavaScript or PCRE style pattern.
+fn strip_delimiters(pattern: &str) -> &str {
+    pattern
+        .strip_prefix('/')
+        .and_then(|rest| {
+            let end = rest.rfind('/')?;
+            rest[end + 1..]
+                .chars()
+                .all(|c| c.is_ascii_alphabetic())
+                .then_some(&rest[..end])
+        })
+        .unwrap_or(pattern)
+}
+
+/// Converts `(?<name>...)` named groups to `(?P<name>...)`, leaving lookbehinds unchanged.
+fn name_groups(pattern: &str) -> String {
+    let mut named = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find("(?<") {
+        let is_lookbehind = matches!(rest[start + 3..].chars().next(), Some('=' | '!'));
+        named.push_str(&rest[..start]);
+        named.push_str(if is_lookbehind { "(?<" } else { "(?P<" });
+        rest = &rest[start + 3..];
+    }
+    named.push_str(rest);
+    named
+}
+
+/// Returns the index of the parenthesis closing the group opened at `open`.
+fn closing_paren(pattern: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    let mut in_class = false;
+    for (i, c) in pattern.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::translate;
+    use crate::Bots;
+
+    #[test]
+    fn translate_pcre() {
+        assert_eq!(translate(r"/^fancyb0t\/\d+/i"), r"^fancyb0t/\d+");
+        assert_eq!(translate(r"(?<ver>\d+)"), r"(?P<ver>\d+)");
+        assert_eq!(
+            translate(r"(?<=compatible; )spidey"),
+            r"(?:compatible; )spidey"
+        );
+        assert_eq!(translate(r"spidey(?=/\d)"), r"spidey(?:/\d)");
+        assert_eq!(translate(r"spidey(?=/)\d"), r"spidey(?=/)\d");
+        assert_eq!(translate(r"(?>a|b)c\hbot"), r"(?:a|b)c[ \t]bot");
+    }
+
+    #[test]
+    fn append_pcre_patterns() {
+        let mut bots = Bots::empty();
+        let errors = bots
+            .append_pcre_compat(&[
+                r"/(?<=compatible; )spidey\/\d/",
+                r"^(?<name>fancyb0t)(?=/)",
+                r"^(?!mozilla).*crawl",
+            ])
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, r"^(?!mozilla).*crawl");
+        assert!(bots.is_bot("Mozilla/5.0 (compatible; Spidey/2)"));
+        assert!(bots.is_bot("FancyB0T/1.0"));
+        assert!(!bots.is_bot("FancyB0T 1.0"));
+        assert!(!bots.is_bot("NiceCrawler/1.0"));
+
+        assert!(bots.append_pcre_compat(&["^slurp"]).is_ok());
+    }
+}
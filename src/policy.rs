@@ -0,0 +1,138 @@
+use crate::{BotCategory, Bots};
+use http::{
+    header::{RETRY_AFTER, USER_AGENT},
+    HeaderMap, Response, StatusCode,
+};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Action to take for a request, see [`BotPolicy::action`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotAction {
+    /// Serve the request
+    Allow,
+    /// Reject the request with `403 Forbidden`
+    Block,
+    /// Reject the request with `429 Too Many Requests`, asking the bot to retry later
+    RateLimit {
+        /// Delay sent in the `Retry-After` header, rounded up to whole seconds
+        retry_after: Duration,
+    },
+}
+
+impl BotAction {
+    /// Returns the rejection response for the action, or `None` for [`BotAction::Allow`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::StatusCode;
+    /// use isbot::BotAction;
+    /// use std::time::Duration;
+    ///
+    /// let action = BotAction::RateLimit { retry_after: Duration::from_secs(120) };
+    /// let response = action.response::<()>().unwrap();
+    ///
+    /// assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    /// assert_eq!(response.headers()["retry-after"], "120");
+    /// ```
+    pub fn response<B: Default>(&self) -> Option<Response<B>> {
+        let mut response = Response::new(B::default());
+        match self {
+            BotAction::Allow => return None,
+            BotAction::Block => *response.status_mut() = StatusCode::FORBIDDEN,
+            BotAction::RateLimit { retry_after } => {
+                let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+                response.headers_mut().insert(RETRY_AFTER, seconds.into());
+            }
+        }
+        Some(response)
+    }
+}
+
+/// Chooses a [`BotAction`] for each request, so middleware can rate limit some bots instead of
+/// blocking all of them
+///
+/// Requests that are not bots are allowed. Bots claiming to be a good bot, see
+/// [`Bots::claims_good_bot`], are allowed unless disabled with [`BotPolicy::allow_good_bots`], bots in
+/// a rate limited category are rate limited, and all other bots are blocked.
+#[derive(Debug)]
+pub struct BotPolicy {
+    bots: Bots,
+    rate_limited: BTreeMap<BotCategory, Duration>,
+    allow_good_bots: bool,
+}
+
+impl BotPolicy {
+    /// Constructs a policy that blocks every bot detected by `bots`, except good bots.
+    pub fn new(bots: Bots) -> Self {
+        BotPolicy {
+            bots,
+            rate_limited: BTreeMap::new(),
+            allow_good_bots: true,
+        }
+    }
+
+    /// Rate limits bots in the category instead of blocking them, asking them to retry after the
+    /// delay.
+    pub fn rate_limit(mut self, category: BotCategory, retry_after: Duration) -> Self {
+        self.rate_limited.insert(category, retry_after);
+        self
+    }
+
+    /// Sets whether bots claiming to be a good bot are allowed, which is the default. The claim is
+    /// not verified, see [`Bots::claims_good_bot`].
+    pub fn allow_good_bots(mut self, allow: bool) -> Self {
+        self.allow_good_bots = allow;
+        self
+    }
+
+    /// Returns the action for a request with the headers, based on its `User-Agent` header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::{header::USER_AGENT, HeaderMap, HeaderValue};
+    /// use isbot::{BotAction, BotCategory, BotPolicy, Bots};
+    /// use std::time::Duration;
+    ///
+    /// let policy = BotPolicy::new(Bots::default())
+    ///     .rate_limit(BotCategory::Archiver, Duration::from_secs(60));
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (compatible; heritrix/3.4.0)"));
+    ///
+    /// assert_eq!(policy.action(&headers), BotAction::RateLimit { retry_after: Duration::from_secs(60) });
+    /// ```
+    pub fn action(&self, headers: &HeaderMap) -> BotAction {
+        if !self.bots.is_bot_headers(headers) {
+            return BotAction::Allow;
+        }
+        let user_agents: Vec<String> = headers
+            .get_all(USER_AGENT)
+            .iter()
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+            .collect();
+        if self.allow_good_bots
+            && user_agents
+                .iter()
+                .any(|user_agent| self.bots.claims_good_bot(user_agent).is_some())
+        {
+            return BotAction::Allow;
+        }
+        self.rate_limited
+            .iter()
+            .find(|(category, _)| {
+                user_agents
+                    .iter()
+                    .any(|user_agent| self.bots.is_category(user_agent, **category))
+            })
+            .map_or(BotAction::Block, |(_, retry_after)| BotAction::RateLimit {
+                retry_after: *retry_after,
+            })
+    }
+
+    /// Returns the rejection response for a request with the headers, or `None` if it is allowed.
+    pub fn response<B: Default>(&self, headers: &HeaderMap) -> Option<Response<B>> {
+        self.action(headers).response()
+    }
+}
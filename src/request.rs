@@ -0,0 +1,117 @@
+use crate::engine::Regex;
+use crate::{Bots, BotsError};
+use std::collections::HashSet;
+
+/// Patterns matched against the request line rather than the user-agent
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestPatterns {
+    patterns: HashSet<String>,
+    regex: Option<Regex>,
+}
+
+impl RequestPatterns {
+    fn is_match(&self, request_line: &str) -> bool {
+        self.regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(request_line))
+    }
+}
+
+impl Bots {
+    /// Appends regular expressions matched against the request line by [`Bots::is_bot_request`].
+    ///
+    /// The request line is the lowercase method and path separated by a space, such as
+    /// `post /xmlrpc.php`, so a pattern can match the path anywhere, like `/wp-login\.php`, or the
+    /// method and path together, like `^post /xmlrpc\.php`. Patterns are trimmed, converted to
+    /// lowercase, and kept separate from the user-agent patterns. Duplicates are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.append_path_patterns(&[r"/wp-login\.php", r"^propfind "]);
+    ///
+    /// assert!(bots.is_bot_request("GET", "/wp-login.php", None));
+    /// assert!(bots.is_bot_request("PROPFIND", "/", None));
+    /// assert!(!bots.is_bot_request("GET", "/", None));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is not a valid regular expression. Use [`Bots::try_append_path_patterns`]
+    /// for patterns that are not known to be valid.
+    pub fn append_path_patterns(&mut self, patterns: &[&str]) {
+        self.try_append_path_patterns(patterns)
+            .expect("Invalid path pattern")
+    }
+
+    /// Appends request line regular expressions like [`Bots::append_path_patterns`], returning an
+    /// error instead of panicking if a pattern is not a valid regular expression.
+    ///
+    /// Each pattern is compiled on its own before any is added, so on error the patterns are left
+    /// unchanged.
+    pub fn try_append_path_patterns(&mut self, patterns: &[&str]) -> Result<(), BotsError> {
+        let patterns: Vec<String> = patterns
+            .iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .map(|pattern| Bots::lowercase_pattern(pattern))
+            .collect();
+        Bots::validate_patterns(&patterns)?;
+
+        let request = &mut self.request_patterns;
+        request.patterns.extend(patterns);
+        request.regex = if request.patterns.is_empty() {
+            None
+        } else {
+            Some(Bots::to_regex(&request.patterns))
+        };
+        Ok(())
+    }
+
+    /// Returns `true` if the user-agent is a known bot or the request line matches a pattern appended
+    /// with [`Bots::append_path_patterns`].
+    ///
+    /// A missing user-agent is not a bot on its own.
+    pub fn is_bot_request(&self, method: &str, path: &str, user_agent: Option<&str>) -> bool {
+        user_agent.is_some_and(|user_agent| self.is_bot(user_agent))
+            || self
+                .request_patterns
+                .is_match(&format!("{} {}", method, path).to_ascii_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    const BROWSER: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+    #[test]
+    fn bot_request() {
+        let mut bots = Bots::default();
+        assert!(!bots.is_bot_request("GET", "/wp-login.php", Some(BROWSER)));
+        assert!(bots.is_bot_request("GET", "/", Some("Googlebot/2.1")));
+
+        bots.append_path_patterns(&[r"/wp-login\.php", r"^post /xmlrpc\.php$"]);
+        assert!(bots.is_bot_request("GET", "/blog/wp-login.php", Some(BROWSER)));
+        assert!(bots.is_bot_request("POST", "/XMLRPC.php", Some(BROWSER)));
+        assert!(!bots.is_bot_request("GET", "/xmlrpc.php", Some(BROWSER)));
+        assert!(!bots.is_bot_request("GET", "/index.html", Some(BROWSER)));
+        assert!(!bots.is_bot_request("GET", "/index.html", None));
+        assert!(!bots.is_bot(BROWSER));
+    }
+
+    #[test]
+    fn try_append_path_patterns() {
+        let mut bots = Bots::default();
+        assert!(bots
+            .try_append_path_patterns(&[r"/wp-login\.php", "(unclosed"])
+            .is_err());
+        assert!(!bots.is_bot_request("GET", "/wp-login.php", Some(BROWSER)));
+
+        bots.try_append_path_patterns(&["  ^propfind  "]).unwrap();
+        assert!(bots.is_bot_request("PROPFIND", "/", Some(BROWSER)));
+    }
+}
@@ -0,0 +1,212 @@
+use crate::engine::Regex;
+use crate::{BotCategory, Bots, BotsError};
+use std::collections::HashSet;
+
+/// Order in which the allowlist and categorized patterns are consulted when they disagree
+///
+/// Whichever policy is set, [excluded categories](crate::BotsBuilder::exclude_category) never match,
+/// and the exclusions of [`Bots::is_bot_scoped`] are applied after the instance layers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionPolicy {
+    /// The allowlist suppresses every match, then categorized and uncategorized patterns are matched
+    #[default]
+    AllowlistFirst,
+    /// A match of a categorized pattern is a bot even if the user-agent is allowed, then the
+    /// allowlist suppresses matches of uncategorized patterns
+    CategoriesFirst,
+}
+
+/// Allowlist and category exclusions consulted by [`Bots::is_bot`] before the bot patterns
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Layers {
+    pub(crate) allow_patterns: HashSet<String>,
+    pub(crate) allow_regex: Option<Regex>,
+    pub(crate) excluded_categories: HashSet<BotCategory>,
+    pub(crate) policy: ResolutionPolicy,
+}
+
+impl Layers {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.allow_regex.is_none() && self.excluded_categories.is_empty()
+    }
+
+    pub(crate) fn update_allow_regex(&mut self) {
+        self.allow_regex = if self.allow_patterns.is_empty() {
+            None
+        } else {
+            Some(Bots::to_regex(&self.allow_patterns))
+        };
+    }
+}
+
+impl Bots {
+    /// Appends allowlist user-agent regular expressions, checked before the bot patterns.
+    ///
+    /// A user-agent matching the allowlist is not a bot, even if it matches a bot pattern, which
+    /// allows a known user-agent through without removing the patterns it matches. Duplicates are
+    /// ignored. Whether the allowlist also overrides categorized patterns depends on
+    /// [`BotsBuilder::resolution`](crate::BotsBuilder::resolution).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// assert!(bots.is_bot("AcmeMonitorBot/1.0"));
+    ///
+    /// bots.allow(&["^acmemonitorbot/"]);
+    /// assert!(!bots.is_bot("AcmeMonitorBot/1.0"));
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is not a valid regular expression. Use [`Bots::try_allow`] for patterns
+    /// that are not known to be valid.
+    pub fn allow(&mut self, patterns: &[&str]) {
+        self.try_allow(patterns).expect("Invalid allow pattern")
+    }
+
+    /// Appends allowlist user-agent regular expressions like [`Bots::allow`], returning an error
+    /// instead of panicking if a pattern is not a valid regular expression.
+    ///
+    /// Each pattern is compiled on its own before any is added, so on error the allowlist is left
+    /// unchanged.
+    pub fn try_allow(&mut self, patterns: &[&str]) -> Result<(), BotsError> {
+        let patterns: Vec<String> = patterns
+            .iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .map(|pattern| self.normalize_pattern(pattern))
+            .collect();
+        Bots::validate_patterns(&patterns)?;
+
+        self.layers.allow_patterns.extend(patterns);
+        self.layers.update_allow_regex();
+        Ok(())
+    }
+
+    /// Resolves the verdict of a normalized user-agent matching a bot pattern against the layers.
+    pub(crate) fn resolve_layers(&self, user_agent: &str, normalized: &str) -> bool {
+        let layers = &self.layers;
+        if layers.policy == ResolutionPolicy::CategoriesFirst
+            && self.is_included_category(normalized)
+        {
+            return true;
+        }
+        if layers
+            .allow_regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(normalized))
+        {
+            return false;
+        }
+        if layers.excluded_categories.is_empty() {
+            return true;
+        }
+        self.weak.is_match(normalized)
+            || self.case_sensitive.is_match(user_agent)
+            || self.matching_patterns(user_agent).iter().any(|pattern| {
+                self.pattern_category(pattern)
+                    .is_none_or(|category| !layers.excluded_categories.contains(&category))
+            })
+    }
+
+    fn is_included_category(&self, normalized: &str) -> bool {
+        self.category_regexes.iter().any(|(category, regex)| {
+            !self.layers.excluded_categories.contains(category) && regex.is_match(normalized)
+        })
+    }
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::{BotCategory, Bots, BotsBuilder, ResolutionPolicy};
+
+    const HEADLESS: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36";
+    const LIGHTHOUSE: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse";
+
+    #[test]
+    fn allowlist_first() {
+        let bots = BotsBuilder::new()
+            .with_defaults()
+            .allow(&["linux x86_64"])
+            .build()
+            .unwrap();
+        assert!(!bots.is_bot(HEADLESS));
+        assert!(!bots.is_bot(LIGHTHOUSE));
+        assert!(bots.is_bot("Googlebot/2.1"));
+    }
+
+    #[test]
+    fn categories_first() {
+        let bots = BotsBuilder::new()
+            .with_defaults()
+            .allow(&["linux x86_64"])
+            .resolution(ResolutionPolicy::CategoriesFirst)
+            .build()
+            .unwrap();
+        assert!(bots.is_bot(HEADLESS));
+        assert!(!bots.is_bot(LIGHTHOUSE));
+    }
+
+    #[test]
+    fn excluded_category() {
+        for policy in [
+            ResolutionPolicy::AllowlistFirst,
+            ResolutionPolicy::CategoriesFirst,
+        ] {
+            let bots = BotsBuilder::new()
+                .with_defaults()
+                .exclude_category(BotCategory::Automation)
+                .resolution(policy)
+                .build()
+                .unwrap();
+            assert!(!bots.is_bot(HEADLESS));
+            assert!(bots.is_bot(LIGHTHOUSE));
+            assert!(
+                bots.is_archiver("Mozilla/5.0 (compatible; heritrix/3.4.0 +http://example.org)")
+            );
+            assert!(!bots.is_automation(HEADLESS));
+        }
+    }
+
+    #[test]
+    fn allow_instance() {
+        let mut bots = Bots::default();
+        assert!(bots.is_bot(LIGHTHOUSE));
+        assert_eq!(bots.which_bot(LIGHTHOUSE), Some("chrome-lighthouse"));
+
+        bots.allow(&["Chrome-Lighthouse", "  chrome-lighthouse "]);
+        assert_eq!(bots.layers.allow_patterns.len(), 1);
+        assert!(!bots.is_bot(LIGHTHOUSE));
+        assert!(bots.is_bot(HEADLESS));
+        assert!(bots.is_bot("Googlebot/2.1"));
+
+        bots.allow(&["headlesschrome"]);
+        assert!(!bots.is_bot(HEADLESS));
+    }
+
+    #[test]
+    fn try_allow() {
+        let mut bots = Bots::default();
+        assert!(bots.try_allow(&["chrome-lighthouse", "(unclosed"]).is_err());
+        assert!(bots.is_bot(LIGHTHOUSE));
+
+        bots.try_allow(&["chrome-lighthouse"]).unwrap();
+        assert!(!bots.is_bot(LIGHTHOUSE));
+    }
+
+    #[test]
+    fn excluded_category_with_case_sensitive_patterns() {
+        let mut bots = BotsBuilder::new()
+            .with_defaults()
+            .exclude_category(BotCategory::Archiver)
+            .build()
+            .unwrap();
+        bots.append_case_sensitive(&["^KoalaClient/"]);
+        assert!(bots.is_bot("KoalaClient/1.0"));
+        assert!(!bots.is_bot("koalaclient/1.0"));
+        assert!(!bots.is_bot("Mozilla/5.0 (compatible; heritrix/3.4.0)"));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::Bots;
+
+/// Incremental matcher for a user-agent received in chunks
+///
+/// Each chunk is appended to an internal buffer. [`BotScanner::push`] returns `Some(true)` as soon as
+/// a pattern matches text that is followed by at least one more character, since later chunks
+/// cannot change that match. Start anchored (`^...`) and plain substring patterns are
+/// therefore decided early. A match touching the end of the buffered text is not final, because
+/// end anchored (`...$`) patterns or trailing `\b` assertions depend on what follows, so those
+/// shapes require the full input and are only decided by [`BotScanner::finish`].
+///
+/// An allowlist, excluded categories, or normalizers can overturn a match depending on the rest of
+/// the user-agent, so an instance using any of them is only decided by [`BotScanner::finish`], which
+/// returns the same verdict as [`Bots::is_bot`] for the complete user-agent.
+///
+/// # Example
+///
+/// ```
+/// use isbot::Bots;
+///
+/// let bots = Bots::default();
+/// let mut scanner = bots.scanner();
+///
+/// assert_eq!(scanner.push("Googleb"), None);
+/// assert_eq!(scanner.push("ot-Image/1.0"), Some(true));
+/// assert!(scanner.finish());
+/// ```
+#[derive(Debug)]
+pub struct BotScanner<'a> {
+    bots: &'a Bots,
+    buffer: String,
+    decided: bool,
+}
+
+impl<'a> BotScanner<'a> {
+    /// Constructs a new scanner matching against the patterns of `bots`.
+    pub fn new(bots: &'a Bots) -> Self {
+        BotScanner {
+            bots,
+            buffer: String::new(),
+            decided: false,
+        }
+    }
+
+    /// Appends a chunk of the user-agent, returning `Some(true)` once the user-agent is known to be a bot.
+    ///
+    /// Returns `None` while undecided. A non-bot verdict is only available from [`BotScanner::finish`].
+    pub fn push(&mut self, chunk: &str) -> Option<bool> {
+        if !self.decided {
+            self.buffer.push_str(chunk);
+            self.decided = self.can_decide_early()
+                && self
+                    .bots
+                    .with_normalized_user_agent(&self.buffer, |normalized| {
+                        self.bots
+                            .user_agents_regex
+                            .find_iter(normalized)
+                            .any(|m| m.end() < normalized.len())
+                    });
+        }
+        self.decided.then_some(true)
+    }
+
+    /// Ends the input and returns `true` if the complete user-agent is a bot, like [`Bots::is_bot`].
+    pub fn finish(self) -> bool {
+        self.decided || self.bots.is_bot(&self.buffer)
+    }
+
+    /// Returns `true` if a match in a prefix of the user-agent is final, which is the case unless
+    /// the layers or normalizers depend on the complete user-agent.
+    fn can_decide_early(&self) -> bool {
+        self.bots.layers.is_empty() && self.bots.normalizers.is_empty()
+    }
+}
+
+impl Bots {
+    /// Returns a [`BotScanner`] to match a user-agent received in chunks.
+    pub fn scanner(&self) -> BotScanner<'_> {
+        BotScanner::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn early_detection() {
+        let bots = Bots::default();
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("Mozilla/5.0 (compatible; Goog"), None);
+        assert_eq!(
+            scanner.push("lebot/2.1; +http://www.google.com/bot.html)"),
+            Some(true)
+        );
+        assert_eq!(scanner.push("ignored"), Some(true));
+        assert!(scanner.finish());
+    }
+
+    #[test]
+    fn end_anchored_requires_full_input() {
+        let bots = Bots::new("lighthouse$");
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("Chrome-Lighthouse"), None);
+        assert_eq!(scanner.push(" extra"), None);
+        assert!(!scanner.finish());
+
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("Chrome-Light"), None);
+        assert_eq!(scanner.push("house"), None);
+        assert!(scanner.finish());
+    }
+
+    #[test]
+    fn browser_not_detected() {
+        let bots = Bots::default();
+        let mut scanner = bots.scanner();
+        assert_eq!(
+            scanner.push("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) "),
+            None
+        );
+        assert_eq!(
+            scanner
+                .push("AppleWebKit/537.36 (KHTML, like Gecko) Chrome/95.0.4638.54 Safari/537.36"),
+            None
+        );
+        assert!(!scanner.finish());
+    }
+
+    #[test]
+    fn allowlist_decided_at_finish() {
+        let mut bots = Bots::default();
+        bots.allow(&["internal-monitor"]);
+
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("Googlebot/2.1 "), None);
+        assert_eq!(scanner.push("(internal-monitor)"), None);
+        assert!(!scanner.finish());
+
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("Googlebot/2.1 "), None);
+        assert!(scanner.finish());
+    }
+
+    #[test]
+    fn matches_is_bot() {
+        let mut bots = Bots::new_case_sensitive("^ABC/");
+        bots.append_case_sensitive(&["^HTTPClient/"]);
+        bots.set_empty_is_bot(true);
+        for user_agent in ["ABC/1.0", "abc/1.0", "HTTPClient/1.0", ""] {
+            let mut scanner = bots.scanner();
+            scanner.push(user_agent);
+            assert_eq!(scanner.finish(), bots.is_bot(user_agent), "{}", user_agent);
+        }
+
+        let mut scanner = bots.scanner();
+        assert_eq!(scanner.push("abc/1.0 "), None);
+        assert_eq!(scanner.push("ABC/1.0"), None);
+        assert!(!scanner.finish());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{BotCategory, Bots};
+use std::collections::HashSet;
+
+/// Patterns and categories to treat as non-bots for a single [`Bots::is_bot_scoped`] call
+///
+/// Build one per route or tenant instead of maintaining a separate [`Bots`] instance for a
+/// small difference in policy.
+///
+/// # Example
+///
+/// ```
+/// use isbot::{BotCategory, ScopedExclusions};
+///
+/// let exclusions = ScopedExclusions::new()
+///     .pattern("Chrome-Lighthouse")
+///     .category(BotCategory::Archiver);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScopedExclusions {
+    patterns: HashSet<String>,
+    categories: HashSet<BotCategory>,
+}
+
+impl ScopedExclusions {
+    /// Constructs an empty set of exclusions.
+    pub fn new() -> Self {
+        ScopedExclusions::default()
+    }
+
+    /// Ignores a bot user-agent regular expression.
+    ///
+    /// The pattern is compared with the patterns of the instance after it is normalized like
+    /// [`Bots::append`], so it is trimmed and, unless the instance is case-sensitive, compared in
+    /// lowercase.
+    pub fn pattern(mut self, pattern: &str) -> Self {
+        self.patterns.insert(pattern.trim().to_string());
+        self
+    }
+
+    /// Treats user-agents identified as the category as non-bots.
+    pub fn category(mut self, category: BotCategory) -> Self {
+        self.categories.insert(category);
+        self
+    }
+
+    /// Returns `true` if there are no exclusions.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.categories.is_empty()
+    }
+}
+
+impl Bots {
+    /// Returns `true` if the user-agent is a known bot, ignoring the scoped exclusions.
+    ///
+    /// A user-agent matching any pattern of an excluded category is not a bot, even if generic
+    /// patterns also match it. Otherwise excluded patterns are ignored and the user-agent is a bot
+    /// if any other pattern matches, including weak and case-sensitive patterns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, Bots, ScopedExclusions};
+    ///
+    /// let bots = Bots::default();
+    /// let archivers_allowed = ScopedExclusions::new().category(BotCategory::Archiver);
+    /// let user_agent = "Mozilla/5.0 (compatible; archive.org_bot +http://www.archive.org/details/archive.org_bot)";
+    ///
+    /// assert!(bots.is_bot(user_agent));
+    /// assert!(!bots.is_bot_scoped(user_agent, &archivers_allowed));
+    /// assert!(bots.is_bot_scoped("Googlebot/2.1", &archivers_allowed));
+    /// ```
+    pub fn is_bot_scoped(&self, user_agent: &str, exclusions: &ScopedExclusions) -> bool {
+        if !self.is_bot(user_agent) {
+            return false;
+        }
+        if exclusions.is_empty() {
+            return true;
+        }
+
+        let matching = self.matching_patterns(user_agent);
+        let excluded_category = matching.iter().any(|pattern| {
+            self.pattern_category(pattern)
+                .is_some_and(|category| exclusions.categories.contains(&category))
+        });
+        if excluded_category {
+            return false;
+        }
+
+        let excluded: HashSet<String> = exclusions
+            .patterns
+            .iter()
+            .map(|pattern| self.normalize_pattern(pattern))
+            .collect();
+        matching.iter().any(|pattern| !excluded.contains(*pattern))
+            || self.weak.is_match(&self.normalize_user_agent(user_agent))
+            || self.case_sensitive.is_match(user_agent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BotCategory, Bots, ScopedExclusions};
+
+    const ARCHIVER: &str =
+        "ia_archiver (+http://www.alexa.com/site/help/webmasters; crawler@alexa.com)";
+    const LIGHTHOUSE: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/78.0.3904.97 Safari/537.36 Chrome-Lighthouse";
+
+    #[test]
+    fn scoped_category() {
+        let bots = Bots::default();
+        let exclusions = ScopedExclusions::new().category(BotCategory::Archiver);
+        assert!(bots.is_bot_scoped(ARCHIVER, &ScopedExclusions::new()));
+        assert!(!bots.is_bot_scoped(ARCHIVER, &exclusions));
+        assert!(bots.is_bot_scoped(LIGHTHOUSE, &exclusions));
+    }
+
+    #[test]
+    fn scoped_pattern() {
+        let bots = Bots::default();
+        let exclusions = ScopedExclusions::new().pattern(" Chrome-Lighthouse ");
+        assert!(!bots.is_bot_scoped(LIGHTHOUSE, &exclusions));
+        assert!(bots.is_bot_scoped(ARCHIVER, &exclusions));
+        assert!(bots.is_bot(LIGHTHOUSE));
+    }
+
+    #[test]
+    fn scoped_case_sensitive() {
+        let mut bots = Bots::default();
+        bots.append_case_sensitive(&["^KoalaClient/"]);
+        let exclusions = ScopedExclusions::new().category(BotCategory::Archiver);
+        assert!(bots.is_bot_scoped("KoalaClient/1.0", &exclusions));
+        assert!(!bots.is_bot_scoped("Koalaclient/1.0", &exclusions));
+        assert!(!bots.is_bot_scoped(ARCHIVER, &exclusions));
+    }
+}
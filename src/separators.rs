@@ -0,0 +1,163 @@
+use crate::Bots;
+use std::env;
+
+/// Splits a pattern list on any of the separators, such as `,` or `;` for lists read from environment
+/// variables or configuration values.
+///
+/// Entries are trimmed, and blank entries are skipped. An entry wrapped in double quotes may contain
+/// separators, and the whitespace inside the quotes is kept. Entries support the same directives as
+/// [`Bots::new`], such as `!anchor` and `!exact`.
+///
+/// A quote that is never closed runs to the end of the text, so the rest of the text is one entry
+/// and keeps its opening quote.
+///
+/// # Example
+///
+/// ```
+/// let patterns = isbot::parse_patterns(r#"googlebot; "bot, inc"  ,^curl/"#, &[',', ';']);
+///
+/// assert_eq!(patterns, vec!["googlebot", "bot, inc", "^curl/"]);
+/// ```
+pub fn parse_patterns(text: &str, separators: &[char]) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in text.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && separators.contains(&c) {
+            entries.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    entries.push(&text[start..]);
+
+    entries
+        .into_iter()
+        .map(str::trim)
+        .filter_map(
+            |entry| match entry.strip_prefix('"').and_then(|e| e.strip_suffix('"')) {
+                Some("") => None,
+                Some(quoted) => Some(Bots::apply_directive(quoted)),
+                None => Bots::parse_line(entry),
+            },
+        )
+        .collect()
+}
+
+impl Bots {
+    /// Constructs a new instance like [`Bots::new`], splitting the entries on any of the separators
+    /// instead of newlines, see [`parse_patterns`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::new_with_separators("Googlebot,^curl/", &[',']);
+    ///
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// assert!(bots.is_bot("curl/7.64.1"));
+    /// ```
+    pub fn new_with_separators(bot_entries: &str, separators: &[char]) -> Self {
+        Bots::from_patterns(
+            parse_patterns(&bot_entries.to_ascii_lowercase(), separators)
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    /// Appends the patterns listed in an environment variable, split on any of the separators, see
+    /// [`parse_patterns`].
+    ///
+    /// Returns an error and leaves the instance unchanged if the variable is not set or is not valid
+    /// Unicode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.append_from_env("EXTRA_BOT_PATTERNS", &[',', ';']).ok();
+    /// ```
+    pub fn append_from_env(&mut self, key: &str, separators: &[char]) -> Result<(), env::VarError> {
+        let value = env::var(key)?;
+        let patterns = parse_patterns(&value, separators);
+        self.append(&patterns.iter().map(String::as_str).collect::<Vec<&str>>());
+        Ok(())
+    }
+
+    /// Constructs a new instance with the default patterns plus the patterns listed in an environment
+    /// variable, such as `ISBOT_EXTRA_PATTERNS`, to add patterns at startup without recompiling.
+    ///
+    /// Patterns in the variable are separated by newlines or commas, see [`parse_patterns`], so a
+    /// pattern containing a comma must be wrapped in double quotes. A missing, empty, or non-Unicode
+    /// variable yields the default patterns.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use isbot::Bots;
+    ///
+    /// // ISBOT_EXTRA_PATTERNS="CustomNewTestB0T,^internal-monitor/"
+    /// let bots = Bots::from_env("ISBOT_EXTRA_PATTERNS");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern in the variable is not a valid regular expression.
+    pub fn from_env(key: &str) -> Self {
+        let mut bots = Bots::default();
+        if env::var_os(key).is_some_and(|value| !value.is_empty()) {
+            bots.append_from_env(key, &['\n', ',']).ok();
+        }
+        bots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_patterns;
+
+    #[test]
+    fn parse_patterns_with_separators() {
+        let expected = vec!["googlebot", "^curl/", "bingpreview/"];
+        assert_eq!(
+            parse_patterns("googlebot,^curl/,bingpreview/", &[',']),
+            expected
+        );
+        assert_eq!(
+            parse_patterns("googlebot; ^curl/ ;bingpreview/;", &[';']),
+            expected
+        );
+        assert_eq!(
+            parse_patterns("googlebot,^curl/;\n\nbingpreview/\n", &[',', ';', '\n']),
+            expected
+        );
+        assert_eq!(
+            parse_patterns("!anchor googlebot,!exact a.b", &[',']),
+            vec!["^(?:googlebot)", r"^a\.b$"]
+        );
+    }
+
+    #[test]
+    fn parse_patterns_with_quoted_separator() {
+        assert_eq!(
+            parse_patterns(r#"googlebot, "acme bot, inc" ,";", "#, &[',']),
+            vec!["googlebot", "acme bot, inc", ";"]
+        );
+        assert_eq!(
+            parse_patterns(r#"" bot ","!exact a b ", """#, &[',']),
+            vec![" bot ", "^a b $"]
+        );
+    }
+
+    #[test]
+    fn parse_patterns_with_unterminated_quote() {
+        assert_eq!(
+            parse_patterns(r#"googlebot, "acme, inc"#, &[',']),
+            vec!["googlebot", r#""acme, inc"#]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::Bots;
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+type VerdictCallback = Box<dyn Fn(&str, bool) + Send + Sync>;
+
+/// Counts of the verdicts observed by a [`ShadowDetector`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ShadowSummary {
+    /// Number of user-agents checked
+    pub observed: usize,
+    /// Number of user-agents that would have been treated as bots
+    pub bots: usize,
+}
+
+/// [`Bots`] instrumented to record verdicts, for observing what would be blocked before enforcing it
+///
+/// Every check is counted in [`ShadowDetector::summary`] and passed to the optional verdict callback,
+/// such as a logger. Acting on the verdict is left to the caller, so a shadow deployment can ignore it.
+///
+/// # Example
+///
+/// ```
+/// use isbot::{Bots, ShadowDetector};
+///
+/// let shadow = ShadowDetector::new(Bots::default())
+///     .on_verdict(|user_agent, is_bot| println!("is_bot={} user_agent={}", is_bot, user_agent));
+///
+/// shadow.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)");
+/// shadow.is_bot("Mozilla/5.0 (Windows NT 10.0; Win64; x64) Firefox/120.0");
+///
+/// let summary = shadow.summary();
+/// assert_eq!(summary.observed, 2);
+/// assert_eq!(summary.bots, 1);
+/// ```
+pub struct ShadowDetector {
+    bots: Bots,
+    callback: Option<VerdictCallback>,
+    observed: AtomicUsize,
+    flagged: AtomicUsize,
+}
+
+impl ShadowDetector {
+    /// Constructs a new instance observing the verdicts of `bots`.
+    pub fn new(bots: Bots) -> Self {
+        ShadowDetector {
+            bots,
+            callback: None,
+            observed: AtomicUsize::new(0),
+            flagged: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets a callback invoked with each user-agent and its verdict.
+    pub fn on_verdict(mut self, callback: impl Fn(&str, bool) + Send + Sync + 'static) -> Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns `true` if the user-agent is a known bot, recording the verdict.
+    pub fn is_bot(&self, user_agent: &str) -> bool {
+        let is_bot = self.bots.is_bot(user_agent);
+        self.observed.fetch_add(1, Ordering::Relaxed);
+        if is_bot {
+            self.flagged.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(callback) = &self.callback {
+            callback(user_agent, is_bot);
+        }
+        is_bot
+    }
+
+    /// Returns the counts of verdicts recorded since construction or the last [`ShadowDetector::reset`].
+    pub fn summary(&self) -> ShadowSummary {
+        ShadowSummary {
+            observed: self.observed.load(Ordering::Relaxed),
+            bots: self.flagged.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets the recorded counts, such as at the start of a new observation period.
+    pub fn reset(&self) {
+        self.observed.store(0, Ordering::Relaxed);
+        self.flagged.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the observed bot patterns.
+    pub fn bots(&self) -> &Bots {
+        &self.bots
+    }
+}
+
+impl fmt::Debug for ShadowDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShadowDetector")
+            .field("bots", &self.bots)
+            .field("summary", &self.summary())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, ShadowDetector, ShadowSummary};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn records_verdicts() {
+        let verdicts = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&verdicts);
+        let shadow = ShadowDetector::new(Bots::default()).on_verdict(move |user_agent, is_bot| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((user_agent.to_string(), is_bot))
+        });
+
+        let user_agents = [
+            "Googlebot/2.1 (+http://www.google.com/bot.html)",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36",
+            "curl/8.4.0",
+        ];
+        for user_agent in user_agents {
+            assert_eq!(shadow.is_bot(user_agent), shadow.bots().is_bot(user_agent));
+        }
+
+        assert_eq!(
+            *verdicts.lock().unwrap(),
+            [
+                (user_agents[0].to_string(), true),
+                (user_agents[1].to_string(), false),
+                (user_agents[2].to_string(), true),
+            ]
+        );
+        assert_eq!(
+            shadow.summary(),
+            ShadowSummary {
+                observed: 3,
+                bots: 2
+            }
+        );
+
+        shadow.reset();
+        assert_eq!(shadow.summary(), ShadowSummary::default());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Bots;
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
+
+impl Bots {
+    /// Constructs a new instance matching shared base patterns and its own local patterns.
+    ///
+    /// Instances built from clones of the same `base` share one allocation of the base patterns, which
+    /// saves memory when running many detectors that mostly overlap, such as one per tenant. Base
+    /// patterns are matched as given, so they should already be lowercase. Local patterns are trimmed
+    /// and converted to lowercase like [`Bots::append`].
+    ///
+    /// Methods that change patterns, such as [`Bots::append`] and [`Bots::remove`], only change the
+    /// local patterns. The base patterns are always matched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    /// use std::sync::Arc;
+    ///
+    /// let base: Arc<[String]> = vec!["googlebot".to_string(), "^curl".to_string()].into();
+    /// let tenant = Bots::with_shared_base(Arc::clone(&base), &["CustomNewTestB0T"]);
+    ///
+    /// assert!(tenant.is_bot("Googlebot/2.1"));
+    /// assert!(tenant.is_bot("CustomNewTestB0T/1.0"));
+    /// assert!(!tenant.is_bot("Mozilla/5.0"));
+    /// ```
+    pub fn with_shared_base(base: Arc<[String]>, local: &[&str]) -> Self {
+        let mut bots = Bots::from_patterns(HashSet::new());
+        bots.shared_base = Some(base);
+        bots.user_agent_patterns = local
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| Bots::lowercase_pattern(p))
+            .collect();
+        bots.update_regex();
+        bots
+    }
+
+    /// Returns the shared base patterns set by [`Bots::with_shared_base`].
+    pub fn shared_base(&self) -> Option<&Arc<[String]>> {
+        self.shared_base.as_ref()
+    }
+
+    /// Returns the local patterns combined with the shared base patterns, as compiled for matching.
+    pub(crate) fn compiled_patterns(&self) -> Cow<'_, HashSet<String>> {
+        match &self.shared_base {
+            Some(base) => {
+                let mut patterns = self.user_agent_patterns.clone();
+                patterns.extend(base.iter().cloned());
+                Cow::Owned(patterns)
+            }
+            None => Cow::Borrowed(&self.user_agent_patterns),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+    use std::sync::Arc;
+
+    #[test]
+    fn shared_base_patterns() {
+        let base: Arc<[String]> = vec!["googlebot".to_string(), "^curl".to_string()].into();
+        let mut first = Bots::with_shared_base(Arc::clone(&base), &[" FirstB0T\n"]);
+        let second = Bots::with_shared_base(Arc::clone(&base), &["secondb0t"]);
+
+        assert!(Arc::ptr_eq(
+            first.shared_base().unwrap(),
+            second.shared_base().unwrap()
+        ));
+        assert_eq!(Arc::strong_count(&base), 3);
+
+        for bots in [&first, &second] {
+            assert!(bots.is_bot("Googlebot/2.1"));
+            assert!(bots.is_bot("curl/8.0"));
+        }
+        assert!(first.is_bot("FirstB0T/1.0"));
+        assert!(!first.is_bot("SecondB0T/1.0"));
+        assert!(second.is_bot("SecondB0T/1.0"));
+        assert!(!second.is_bot("FirstB0T/1.0"));
+        assert_eq!(first.matching_patterns("Googlebot/2.1"), vec!["googlebot"]);
+
+        first.remove(&["googlebot", "firstb0t"]);
+        assert!(first.is_bot("Googlebot/2.1"));
+        assert!(!first.is_bot("FirstB0T/1.0"));
+    }
+}
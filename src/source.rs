@@ -0,0 +1,71 @@
+use crate::Bots;
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Default patterns grouped by the bot data source their user-agents were found in
+const BOT_SOURCES: &str = include_str!("bot_sources.txt");
+
+impl Bots {
+    /// Returns the bundled data source a default pattern originated from, such as `"myip.ms"` or
+    /// `"ua-parser"`, to know which upstream to report corrections to.
+    ///
+    /// Patterns added from the curated bot list in this repository return `"curated"`. Patterns
+    /// without a recorded source, including patterns that are not default patterns, return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.source_of("bingpreview/"), Some("ua-parser"));
+    /// assert_eq!(bots.source_of("customnewtestb0t"), None);
+    /// ```
+    pub fn source_of(&self, pattern: &str) -> Option<&'static str> {
+        default_sources().get(pattern).copied()
+    }
+}
+
+fn default_sources() -> &'static HashMap<&'static str, &'static str> {
+    static DEFAULT_SOURCES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    DEFAULT_SOURCES.get_or_init(|| parse_sources(BOT_SOURCES))
+}
+
+fn parse_sources(entries: &str) -> HashMap<&str, &str> {
+    let mut sources = HashMap::new();
+    let mut current = None;
+    for line in entries.lines().filter(|l| !l.trim().is_empty()) {
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = Some(section);
+        } else if let Some(source) = current {
+            sources.insert(line, source);
+        }
+    }
+    sources
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use super::{parse_sources, BOT_SOURCES};
+    use crate::Bots;
+
+    #[test]
+    fn sourced_patterns_are_default_patterns() {
+        let bots = Bots::default();
+        for pattern in parse_sources(BOT_SOURCES).keys() {
+            assert!(
+                bots.user_agent_patterns.contains(*pattern),
+                "Sourced pattern is not a default pattern: {}",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn source_of() {
+        let bots = Bots::default();
+        assert_eq!(bots.source_of("bingpreview/"), Some("ua-parser"));
+        assert_eq!(bots.source_of("^curl"), Some("myip.ms"));
+        assert_eq!(bots.source_of("customnewtestb0t"), None);
+    }
+}
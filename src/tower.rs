@@ -0,0 +1,177 @@
+use crate::{BotPolicy, Bots};
+use http::{HeaderMap, Request, Response, StatusCode};
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// [`Layer`] rejecting requests whose `User-Agent` header is a known bot, for axum, hyper, or any
+/// other [tower](https://crates.io/crates/tower) based server
+///
+/// Bot requests are answered with an empty body and the configured status, `403 Forbidden` by
+/// default, without calling the inner service. The header is checked with [`Bots::is_bot_headers`],
+/// so requests without a `User-Agent` header are passed through. A layer constructed with
+/// [`BotFilterLayer::with_policy`] instead answers with the response of the [`BotAction`] chosen by
+/// the policy, such as `429 Too Many Requests` for rate limited bots. The [`Bots`] instance or
+/// policy is shared behind an [`Arc`] by every service the layer creates.
+///
+/// [`BotAction`]: crate::BotAction
+///
+/// # Example
+///
+/// ```
+/// use http::StatusCode;
+/// use isbot::{BotFilterLayer, Bots};
+/// use std::sync::Arc;
+///
+/// let layer = BotFilterLayer::new(Arc::new(Bots::default())).status(StatusCode::NOT_FOUND);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BotFilterLayer {
+    filter: Filter,
+}
+
+impl BotFilterLayer {
+    /// Constructs a new layer rejecting bots with `403 Forbidden`.
+    pub fn new(bots: Arc<Bots>) -> Self {
+        BotFilterLayer {
+            filter: Filter::Bots {
+                bots,
+                status: StatusCode::FORBIDDEN,
+            },
+        }
+    }
+
+    /// Constructs a new layer answering each request with the action chosen by the policy, see
+    /// [`BotPolicy::action`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{BotCategory, BotFilterLayer, BotPolicy, Bots};
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// let policy = BotPolicy::new(Bots::default())
+    ///     .rate_limit(BotCategory::Archiver, Duration::from_secs(60));
+    /// let layer = BotFilterLayer::with_policy(Arc::new(policy));
+    /// ```
+    pub fn with_policy(policy: Arc<BotPolicy>) -> Self {
+        BotFilterLayer {
+            filter: Filter::Policy(policy),
+        }
+    }
+
+    /// Sets the status of the response to bot requests.
+    ///
+    /// A layer constructed with [`BotFilterLayer::with_policy`] is not changed, since the status
+    /// comes from the action chosen by the policy.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        if let Filter::Bots {
+            status: current, ..
+        } = &mut self.filter
+        {
+            *current = status;
+        }
+        self
+    }
+}
+
+impl<S> Layer<S> for BotFilterLayer {
+    type Service = BotFilter<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BotFilter {
+            inner,
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+/// How a [`BotFilter`] decides which requests to reject
+#[derive(Debug, Clone)]
+enum Filter {
+    Bots { bots: Arc<Bots>, status: StatusCode },
+    Policy(Arc<BotPolicy>),
+}
+
+impl Filter {
+    /// Returns the rejection response for a request with the headers, or `None` if it is served.
+    fn response<B: Default>(&self, headers: &HeaderMap) -> Option<Response<B>> {
+        match self {
+            Filter::Bots { bots, status } => bots.is_bot_headers(headers).then(|| {
+                let mut response = Response::new(B::default());
+                *response.status_mut() = *status;
+                response
+            }),
+            Filter::Policy(policy) => policy.response(headers),
+        }
+    }
+}
+
+/// Service created by [`BotFilterLayer`]
+#[derive(Debug, Clone)]
+pub struct BotFilter<S> {
+    inner: S,
+    filter: Filter,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for BotFilter<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = BotFilterFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        match self.filter.response(request.headers()) {
+            Some(response) => BotFilterFuture::Rejected {
+                response: Some(response),
+            },
+            None => BotFilterFuture::Inner {
+                future: self.inner.call(request),
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`BotFilter`]
+    #[derive(Debug)]
+    #[project = BotFilterFutureProj]
+    pub enum BotFilterFuture<F, B> {
+        /// The request was passed to the inner service
+        Inner {
+            #[pin]
+            future: F,
+        },
+        /// The request was rejected, holding the response until it is polled
+        Rejected {
+            response: Option<Response<B>>,
+        },
+    }
+}
+
+impl<F, B, E> Future for BotFilterFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = Result<Response<B>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            BotFilterFutureProj::Inner { future } => future.poll(cx),
+            BotFilterFutureProj::Rejected { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("BotFilterFuture polled after completion"))),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::engine::Regex;
+use crate::Bots;
+use std::sync::OnceLock;
+
+/// Default patterns that match a generic bot token rather than a specific bot
+const GENERIC_PATTERNS: &[&str] = &[
+    "[ ]+bot",
+    r"^[a-z.0-9/ \-_]*bot",
+    r"bot($|[/\);-]+)",
+    "crawler",
+    "fetcher",
+    "scraper",
+    "spider",
+];
+
+/// Tokens that strongly suggest a bot wherever they appear in the user-agent
+const STRONG_TOKENS: &[&str] = &["bot", "crawler", "fetcher", "scraper", "spider"];
+
+/// [`Bots::bot_score`] weight of a specific pattern match, or of reaching the weak pattern threshold
+const SPECIFIC_MATCH_SCORE: f32 = 0.6;
+
+/// [`Bots::bot_score`] weight of a match of generic patterns only
+const GENERIC_MATCH_SCORE: f32 = 0.3;
+
+/// [`Bots::bot_score`] weight of each matching pattern after the first, up to [`MAX_EXTRA_MATCHES`]
+const EXTRA_MATCH_SCORE: f32 = 0.1;
+
+const MAX_EXTRA_MATCHES: usize = 2;
+
+/// [`Bots::bot_score`] weight of a strong bot token
+const STRONG_TOKEN_SCORE: f32 = 0.2;
+
+/// [`Bots::bot_score`] weight of a user-agent without any browser token
+const NO_BROWSER_TOKEN_SCORE: f32 = 0.2;
+
+/// Generic bot token preceded by a name, such as `googlebot` or `exabot`
+const VENDOR_TOKEN: &str = r"[a-z0-9]{2,}(bot|crawler|fetcher|scraper|spider)\b";
+
+/// Three-state verdict of [`Bots::triage`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// The user-agent is a known bot
+    Bot,
+    /// The user-agent is structured like a browser and matches no bot pattern
+    Human,
+    /// The user-agent only matches generic or weak patterns, or is not structured like a browser
+    Uncertain,
+}
+
+impl Bots {
+    /// Returns whether the user-agent is a bot, a human, or uncertain, such as to serve uncertain
+    /// traffic a challenge instead of blocking or allowing it.
+    ///
+    /// A user-agent is [`Verdict::Uncertain`] when it only matches a generic bot token without a name,
+    /// such as `(compatible; bot)`, when its weak pattern score is above zero but below the threshold,
+    /// or when it is [unknown](Bots::is_unknown). A user-agent matching a bot pattern that the
+    /// allowlist or an excluded category lets through is [`Verdict::Human`].
+    ///
+    /// [`Verdict::Bot`] is always a bot for [`Bots::is_bot`] and [`Verdict::Human`] never is. An
+    /// uncertain user-agent matching a generic bot token is a bot for [`Bots::is_bot`], while other
+    /// uncertain user-agents are not. The detection hook is not called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::{Bots, Verdict};
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.triage("Googlebot/2.1 (+http://www.google.com/bot.html)"), Verdict::Bot);
+    /// assert_eq!(bots.triage("Mozilla/5.0 (compatible; Bot/1.0)"), Verdict::Uncertain);
+    /// assert_eq!(bots.triage("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0"), Verdict::Human);
+    /// ```
+    pub fn triage(&self, user_agent: &str) -> Verdict {
+        if user_agent.trim().is_empty() {
+            return if self.empty_is_bot {
+                Verdict::Bot
+            } else {
+                Verdict::Uncertain
+            };
+        }
+
+        let normalized = self.normalize_user_agent(user_agent);
+        let pattern_match = self.user_agents_regex.is_match(&normalized);
+        let certain_match =
+            self.weak.is_match(&normalized) || self.case_sensitive.is_match(user_agent);
+        if self.detect(user_agent) {
+            let specific = certain_match
+                || vendor_token().is_match(&self.lowercase_normalized(&normalized))
+                || self
+                    .matching_patterns(user_agent)
+                    .iter()
+                    .any(|pattern| !GENERIC_PATTERNS.contains(pattern));
+            return if specific {
+                Verdict::Bot
+            } else {
+                Verdict::Uncertain
+            };
+        }
+
+        if pattern_match || certain_match {
+            // Matched, but let through by the allowlist or an excluded category
+            Verdict::Human
+        } else if self.weak.score(&normalized) > 0.0 || self.is_unknown(user_agent) {
+            Verdict::Uncertain
+        } else {
+            Verdict::Human
+        }
+    }
+}
+
+impl Bots {
+    /// Returns a score from `0.0` to `1.0` of how likely the user-agent is a bot, such as to choose a
+    /// threshold instead of relying on the [`Bots::is_bot`] verdict.
+    ///
+    /// The score is the sum of the following, capped at `1.0`:
+    ///
+    /// | Evidence | Score |
+    /// | -------- | ----- |
+    /// | a specific pattern matches, such as `googlebot`, or the weak pattern threshold is reached | `0.6` |
+    /// | otherwise, only generic patterns match, such as `[ ]+bot` | `0.3` |
+    /// | each matching pattern after the first, for at most two | `0.1` |
+    /// | the user-agent contains `bot`, `crawler`, `fetcher`, `scraper`, or `spider` | `0.2` |
+    /// | the user-agent contains no browser token, see [`Bots::is_unknown`] | `0.2` |
+    ///
+    /// The score only depends on the user-agent and the patterns, so it is deterministic. A named bot
+    /// such as Googlebot scores `1.0`, an HTTP client such as curl `0.8`, an unknown tool without a bot
+    /// token `0.2`, and a regular browser `0.0`. An empty user-agent scores `1.0` if it is set to be a
+    /// bot with [`Bots::set_empty_is_bot`] and `0.0` otherwise, and allowlisted user-agents score `0.0`.
+    /// The detection hook is not called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.bot_score("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"), 1.0);
+    /// assert_eq!(bots.bot_score("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"), 0.0);
+    /// ```
+    pub fn bot_score(&self, user_agent: &str) -> f32 {
+        if user_agent.trim().is_empty() {
+            return if self.empty_is_bot { 1.0 } else { 0.0 };
+        }
+        let normalized = self.normalize_user_agent(user_agent);
+        if !self.layers.is_empty() && !self.resolve_layers(user_agent, &normalized) {
+            return 0.0;
+        }
+
+        let matches = self.pattern_set().matches(&normalized);
+        let mut score = if matches
+            .iter()
+            .any(|pattern| !GENERIC_PATTERNS.contains(pattern))
+            || self.weak.is_match(&normalized)
+            || self.case_sensitive.is_match(user_agent)
+        {
+            SPECIFIC_MATCH_SCORE
+        } else if !matches.is_empty() {
+            GENERIC_MATCH_SCORE
+        } else {
+            0.0
+        };
+        score += EXTRA_MATCH_SCORE * matches.len().saturating_sub(1).min(MAX_EXTRA_MATCHES) as f32;
+        let lowercase = self.lowercase_normalized(&normalized);
+        if STRONG_TOKENS.iter().any(|token| lowercase.contains(token)) {
+            score += STRONG_TOKEN_SCORE;
+        }
+        if !self
+            .browser_tokens
+            .iter()
+            .any(|token| lowercase.contains(token.as_str()))
+        {
+            score += NO_BROWSER_TOKEN_SCORE;
+        }
+        score.min(1.0)
+    }
+}
+
+fn vendor_token() -> &'static Regex {
+    static VENDOR_TOKEN_REGEX: OnceLock<Regex> = OnceLock::new();
+    VENDOR_TOKEN_REGEX.get_or_init(|| Regex::new(VENDOR_TOKEN).unwrap())
+}
+
+#[cfg(all(test, feature = "include-default-bots"))]
+mod tests {
+    use crate::{Bots, Verdict};
+
+    #[test]
+    fn generic_patterns_are_default_patterns() {
+        let bots = Bots::default();
+        for pattern in super::GENERIC_PATTERNS {
+            assert!(bots.user_agent_patterns.contains(*pattern), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn triage() {
+        let mut bots = Bots::default();
+        assert_eq!(
+            bots.triage("Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)"),
+            Verdict::Bot
+        );
+        assert_eq!(bots.triage("curl/7.64.1"), Verdict::Bot);
+        assert_eq!(
+            bots.triage("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"),
+            Verdict::Human
+        );
+
+        let generic = "Mozilla/5.0 (compatible; Bot/1.0)";
+        assert_eq!(bots.triage(generic), Verdict::Uncertain);
+        assert!(bots.is_bot(generic));
+
+        let unknown = "MyInternalTool/1.0";
+        assert_eq!(bots.triage(unknown), Verdict::Uncertain);
+        assert!(!bots.is_bot(unknown));
+
+        bots.append_weak(&[("crawl", 0.5)]);
+        assert_eq!(
+            bots.triage("Mozilla/5.0 (X11; Linux x86_64; crawl) AppleWebKit/537.36"),
+            Verdict::Uncertain
+        );
+    }
+
+    #[test]
+    fn bot_score() {
+        let mut bots = Bots::default();
+        let googlebot = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        let chrome = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        assert!(bots.bot_score(googlebot) > 0.95);
+        assert_eq!(bots.bot_score(googlebot), bots.bot_score(googlebot));
+        assert_eq!(bots.bot_score(chrome), 0.0);
+        assert_eq!(bots.bot_score("curl/7.64.1"), 0.8);
+        assert_eq!(bots.bot_score("MyInternalTool/1.0"), 0.2);
+        assert_eq!(bots.bot_score(""), 0.0);
+
+        let generic = bots.bot_score("Mozilla/5.0 (compatible; Bot/1.0) AppleWebKit/537.36");
+        assert!(
+            generic > 0.0 && generic < bots.bot_score(googlebot),
+            "{}",
+            generic
+        );
+
+        bots.set_empty_is_bot(true);
+        assert_eq!(bots.bot_score("  "), 1.0);
+
+        let bots = Bots::builder()
+            .with_defaults()
+            .allow(&["googlebot"])
+            .build()
+            .unwrap();
+        assert_eq!(bots.bot_score(googlebot), 0.0);
+    }
+
+    #[test]
+    fn triage_agrees_with_is_bot() {
+        let googlebot = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        let mut bots = Bots::default();
+        bots.allow(&["googlebot"]);
+        assert!(!bots.is_bot(googlebot));
+        assert_eq!(bots.triage(googlebot), Verdict::Human);
+
+        assert_eq!(bots.triage(""), Verdict::Uncertain);
+        bots.set_empty_is_bot(true);
+        assert!(bots.is_bot(""));
+        assert_eq!(bots.triage(""), Verdict::Bot);
+
+        bots.append_case_sensitive(&["^HTTPClient/"]);
+        assert!(bots.is_bot("HTTPClient/1.0"));
+        assert_eq!(bots.triage("HTTPClient/1.0"), Verdict::Bot);
+
+        for user_agent in [
+            googlebot,
+            "",
+            "HTTPClient/1.0",
+            "Mozilla/5.0 (compatible; Bot/1.0)",
+            "MyInternalTool/1.0",
+            "curl/7.64.1",
+        ] {
+            match bots.triage(user_agent) {
+                Verdict::Bot => assert!(bots.is_bot(user_agent), "{}", user_agent),
+                Verdict::Human => assert!(!bots.is_bot(user_agent), "{}", user_agent),
+                Verdict::Uncertain => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::Bots;
+
+/// Rendering engine and legacy browser tokens found in real browser user-agents
+pub(crate) const DEFAULT_BROWSER_TOKENS: &[&str] = &[
+    "applewebkit",
+    "gecko",
+    "khtml",
+    "trident/",
+    "presto/",
+    "msie ",
+];
+
+impl Bots {
+    /// Returns `true` if the user-agent is neither a known bot nor structured like a browser.
+    ///
+    /// A user-agent is structured like a browser if it contains a browser token, which by default are
+    /// rendering engine tokens such as `AppleWebKit`, `Gecko`, or `Trident/`. This catches scripts and
+    /// HTTP clients that are not in the bot patterns, such as a stripped or empty user-agent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert!(bots.is_unknown("MyInternalTool/1.0"));
+    /// assert!(bots.is_unknown(""));
+    /// assert!(!bots.is_unknown("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    /// assert!(!bots.is_unknown("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0"));
+    /// ```
+    pub fn is_unknown(&self, user_agent: &str) -> bool {
+        if self.is_bot(user_agent) {
+            return false;
+        }
+        let user_agent = user_agent.to_ascii_lowercase();
+        !self
+            .browser_tokens
+            .iter()
+            .any(|token| user_agent.contains(token.as_str()))
+    }
+
+    /// Replaces the browser tokens used by [`Bots::is_unknown`].
+    ///
+    /// Tokens are matched as case-insensitive substrings rather than regular expressions.
+    pub fn set_browser_tokens(&mut self, tokens: &[&str]) {
+        self.browser_tokens = tokens
+            .iter()
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_ascii_lowercase())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn unknown_user_agents() {
+        let bots = Bots::new("googlebot");
+        assert!(bots.is_unknown("python-requests/2.0"));
+        assert!(bots.is_unknown("curl/7.0"));
+        assert!(bots.is_unknown("Go-http-client/1.1"));
+        assert!(bots.is_unknown(""));
+        assert!(!bots.is_unknown("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"));
+        assert!(!bots.is_unknown("Mozilla/4.0 (compatible; MSIE 6.0; Windows NT 5.1)"));
+        assert!(!bots.is_unknown("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+        assert!(bots.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+
+        let bots = Bots::default();
+        assert!(!bots.is_unknown("curl/7.0"));
+        assert!(bots.is_bot("curl/7.0"));
+    }
+
+    #[test]
+    fn custom_browser_tokens() {
+        let mut bots = Bots::empty();
+        bots.set_browser_tokens(&["Dalvik/"]);
+        assert!(!bots.is_unknown("Dalvik/2.1.0 (Linux; U; Android 8.0.0)"));
+        assert!(bots.is_unknown("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36"));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::Bots;
+
+/// Good bot name, lowercase user-agent token, and the reverse DNS suffixes of its crawler hosts
+const GOOD_BOTS: &[(&str, &str, &[&str])] = &[
+    ("Googlebot", "googlebot", &[".googlebot.com", ".google.com"]),
+    ("Bingbot", "bingbot", &[".search.msn.com"]),
+    ("Applebot", "applebot", &[".applebot.apple.com"]),
+    ("Baiduspider", "baiduspider", &[".baidu.com", ".baidu.jp"]),
+    (
+        "YandexBot",
+        "yandex",
+        &[".yandex.ru", ".yandex.net", ".yandex.com"],
+    ),
+    ("Yahoo! Slurp", "yahoo! slurp", &[".crawl.yahoo.net"]),
+];
+
+impl Bots {
+    /// Returns the name of the good bot the user-agent claims to be, such as `"Googlebot"`.
+    ///
+    /// Any client can send a good bot user-agent, so the claim is not proof. Callers that treat good
+    /// bots differently should verify the claim with a reverse DNS lookup of the client IP, checking
+    /// the host against [`Bots::verification_suffixes`] and resolving it back to the same IP.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.claims_good_bot("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"), Some("Googlebot"));
+    /// assert_eq!(bots.claims_good_bot("curl/7.64.1"), None);
+    /// ```
+    pub fn claims_good_bot(&self, user_agent: &str) -> Option<&'static str> {
+        let user_agent = user_agent.to_ascii_lowercase();
+        GOOD_BOTS
+            .iter()
+            .find(|(_, token, _)| user_agent.contains(token))
+            .map(|(name, _, _)| *name)
+    }
+
+    /// Returns the reverse DNS host suffixes of a good bot returned by [`Bots::claims_good_bot`], or
+    /// `None` for any other name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let bots = Bots::default();
+    ///
+    /// assert_eq!(bots.verification_suffixes("Bingbot"), Some(&[".search.msn.com"][..]));
+    /// ```
+    pub fn verification_suffixes(&self, bot: &str) -> Option<&'static [&'static str]> {
+        GOOD_BOTS
+            .iter()
+            .find(|(name, _, _)| *name == bot)
+            .map(|(_, _, suffixes)| *suffixes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bots;
+
+    #[test]
+    fn claims_good_bot() {
+        let bots = Bots::empty();
+        let spoofed = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        assert_eq!(bots.claims_good_bot(spoofed), Some("Googlebot"));
+        assert_eq!(
+            bots.claims_good_bot(
+                "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)"
+            ),
+            Some("Bingbot")
+        );
+        assert_eq!(
+            bots.claims_good_bot(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0"
+            ),
+            None
+        );
+
+        let suffixes = bots.verification_suffixes("Googlebot").unwrap();
+        assert!(suffixes.contains(&".googlebot.com"));
+        assert_eq!(bots.verification_suffixes("googlebot"), None);
+    }
+}
@@ -0,0 +1,96 @@
+//! JavaScript bindings for WebAssembly, built with [wasm-bindgen](https://crates.io/crates/wasm-bindgen)
+
+use crate::Bots;
+use wasm_bindgen::prelude::*;
+
+/// Returns `true` if the user-agent is a known bot, using the shared default instance from
+/// [`Bots::global`].
+///
+/// The default patterns are embedded in the WebAssembly module, so no pattern file is fetched at
+/// runtime.
+#[wasm_bindgen(js_name = isBot)]
+pub fn is_bot(user_agent: &str) -> bool {
+    Bots::global().is_bot(user_agent)
+}
+
+/// JavaScript wrapper of [`Bots`] to customize the patterns, exported as `Bots`
+///
+/// # Example
+///
+/// ```
+/// use isbot::wasm::WasmBots;
+///
+/// let mut bots = WasmBots::new();
+/// bots.append(vec!["CustomNewTestB0T".to_string()]);
+///
+/// assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+/// ```
+#[wasm_bindgen(js_name = Bots)]
+#[derive(Debug, Clone)]
+pub struct WasmBots {
+    bots: Bots,
+}
+
+#[wasm_bindgen(js_class = Bots)]
+impl WasmBots {
+    /// Constructs a new instance with the default patterns.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> WasmBots {
+        WasmBots {
+            bots: Bots::global().clone(),
+        }
+    }
+
+    /// Constructs a new instance with newline delimited patterns, like [`Bots::try_new`], throwing
+    /// an error if a pattern is not a valid regular expression.
+    #[wasm_bindgen(js_name = fromPatterns)]
+    pub fn from_patterns(bot_entries: &str) -> Result<WasmBots, JsError> {
+        Ok(WasmBots {
+            bots: Bots::try_new(bot_entries)?,
+        })
+    }
+
+    /// Returns `true` if the user-agent is a known bot, like [`Bots::is_bot`].
+    #[wasm_bindgen(js_name = isBot)]
+    pub fn is_bot(&self, user_agent: &str) -> bool {
+        self.bots.is_bot(user_agent)
+    }
+
+    /// Appends patterns like [`Bots::try_append`], throwing an error if a pattern is not a valid
+    /// regular expression.
+    pub fn append(&mut self, patterns: Vec<String>) -> Result<(), JsError> {
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        Ok(self.bots.try_append(&patterns)?)
+    }
+
+    /// Removes patterns like [`Bots::remove`].
+    pub fn remove(&mut self, patterns: Vec<String>) {
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        self.bots.remove(&patterns);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{is_bot, WasmBots};
+
+    #[test]
+    fn wrapper_append_and_remove() {
+        assert!(is_bot("Googlebot"));
+        assert!(!is_bot("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"));
+
+        let mut bots = WasmBots::new();
+        assert!(bots.is_bot("Chrome-Lighthouse"));
+        bots.remove(vec!["Chrome-Lighthouse".to_string()]);
+        assert!(!bots.is_bot("Chrome-Lighthouse"));
+        assert!(is_bot("Chrome-Lighthouse"));
+
+        bots.append(vec!["CustomNewTestB0T".to_string()]).unwrap();
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+
+        let bots = WasmBots::from_patterns("^curl/").unwrap();
+        assert!(bots.is_bot("curl/7.64.1"));
+        assert!(!bots.is_bot("Googlebot"));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::engine::RegexSet;
+use crate::{Bots, BotsError};
+use std::collections::HashMap;
+
+/// Default score weak patterns must reach for a user-agent to be a bot
+const DEFAULT_WEAK_THRESHOLD: f32 = 1.0;
+
+/// Low-confidence patterns that only identify a bot when their combined weight reaches a threshold
+#[derive(Debug, Clone)]
+pub(crate) struct WeakPatterns {
+    weights: HashMap<String, f32>,
+    set: RegexSet,
+    set_patterns: Vec<String>,
+    set_weights: Vec<f32>,
+    threshold: f32,
+}
+
+impl Default for WeakPatterns {
+    fn default() -> Self {
+        WeakPatterns {
+            weights: HashMap::new(),
+            set: RegexSet::empty(),
+            set_patterns: Vec::new(),
+            set_weights: Vec::new(),
+            threshold: DEFAULT_WEAK_THRESHOLD,
+        }
+    }
+}
+
+impl WeakPatterns {
+    fn update_set(&mut self) {
+        let mut entries = self.weights.iter().collect::<Vec<(&String, &f32)>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        self.set = RegexSet::new(entries.iter().map(|(pattern, _)| pattern.as_str()))
+            .expect("Invalid weak pattern");
+        self.set_patterns = entries
+            .iter()
+            .map(|(pattern, _)| (*pattern).clone())
+            .collect();
+        self.set_weights = entries.iter().map(|(_, weight)| **weight).collect();
+    }
+
+    /// Returns the combined weight of the weak patterns matching the lowercase user-agent.
+    pub(crate) fn score(&self, user_agent: &str) -> f32 {
+        if self.weights.is_empty() {
+            return 0.0;
+        }
+        self.set
+            .matches(user_agent)
+            .into_iter()
+            .map(|index| self.set_weights[index])
+            .sum()
+    }
+
+    pub(crate) fn is_match(&self, user_agent: &str) -> bool {
+        !self.weights.is_empty() && self.score(user_agent) >= self.threshold
+    }
+
+    /// Returns the weak patterns matching the lowercase user-agent, in sorted pattern order.
+    pub(crate) fn matches(&self, user_agent: &str) -> Vec<&str> {
+        if self.weights.is_empty() {
+            return Vec::new();
+        }
+        self.set
+            .matches(user_agent)
+            .into_iter()
+            .map(|index| self.set_patterns[index].as_str())
+            .collect()
+    }
+}
+
+impl Bots {
+    /// Appends weak bot user-agent regular expressions, each with a weight.
+    ///
+    /// Weak patterns are generic tokens such as `spider` or `crawl` that are not enough evidence on
+    /// their own. A user-agent only matches when the weights of all matching weak patterns add up to
+    /// the threshold set by [`Bots::set_weak_threshold`], which defaults to `1.0`. Regular patterns
+    /// still match immediately. Appending a weak pattern that is already a regular pattern turns it
+    /// into a weak one. Patterns are trimmed and converted to lowercase like [`Bots::append`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use isbot::Bots;
+    ///
+    /// let mut bots = Bots::default();
+    /// bots.append_weak(&[("spider", 0.5), ("crawl", 0.5)]);
+    ///
+    /// assert!(!bots.is_bot("WebSpider/1.0"));
+    /// assert!(bots.is_bot("WebSpider/1.0 (crawling the web)"));
+    /// assert!(bots.is_bot("Googlebot/2.1"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is not a valid regular expression. Use [`Bots::try_append_weak`] for
+    /// patterns that are not known to be valid.
+    pub fn append_weak(&mut self, patterns: &[(&str, f32)]) {
+        self.try_append_weak(patterns).expect("Invalid bot pattern")
+    }
+
+    /// Appends weak bot user-agent regular expressions like [`Bots::append_weak`], returning an error
+    /// instead of panicking if a pattern is not a valid regular expression.
+    ///
+    /// Each pattern is compiled on its own before any is added, so on error the patterns are left
+    /// unchanged.
+    pub fn try_append_weak(&mut self, patterns: &[(&str, f32)]) -> Result<(), BotsError> {
+        let patterns: Vec<(String, f32)> = patterns
+            .iter()
+            .filter(|(pattern, _)| !pattern.trim().is_empty())
+            .map(|(pattern, weight)| (self.normalize_pattern(pattern), *weight))
+            .collect();
+        Bots::validate_patterns(patterns.iter().map(|(pattern, _)| pattern))?;
+
+        for (pattern, weight) in patterns {
+            self.user_agent_patterns.remove(&pattern);
+            self.weak.weights.insert(pattern, weight);
+        }
+        self.weak.update_set();
+        self.update_regex();
+        Ok(())
+    }
+
+    /// Sets the combined weight weak patterns must reach for a user-agent to be a bot.
+    pub fn set_weak_threshold(&mut self, threshold: f32) {
+        self.weak.threshold = threshold;
+    }
+
+    /// Returns the combined weight of the weak patterns matching the user-agent.
+    pub fn weak_score(&self, user_agent: &str) -> f32 {
+        self.weak.score(&self.normalize_user_agent(user_agent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bots, BotsError};
+
+    #[test]
+    fn weak_threshold() {
+        let mut bots = Bots::default();
+        bots.append_weak(&[("spider", 0.5), ("crawl", 0.5), ("fetch", 0.25)]);
+        assert!(!bots.user_agent_patterns.contains("spider"));
+
+        assert!(!bots.is_bot("NiceSpider/2.0"));
+        assert!(bots.is_bot("NiceSpider/2.0 (crawl)"));
+        assert_eq!(bots.weak_score("NiceSpider/2.0 (crawl)"), 1.0);
+        assert!(
+            bots.is_bot("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)")
+        );
+
+        bots.set_weak_threshold(1.5);
+        assert!(!bots.is_bot("NiceSpider/2.0 (crawl)"));
+        assert!(!bots.is_bot("NiceSpider/2.0 (crawl; fetch)"));
+        bots.set_weak_threshold(1.25);
+        assert!(bots.is_bot("NiceSpider/2.0 (crawl; fetch)"));
+    }
+
+    #[test]
+    fn try_append_weak() {
+        let mut bots = Bots::new("googlebot");
+        assert!(matches!(
+            bots.try_append_weak(&[("spider", 0.5), ("(unclosed", 0.5)]),
+            Err(BotsError::InvalidPattern { pattern, .. }) if pattern == "(unclosed"
+        ));
+        assert_eq!(bots.weak_score("Spider"), 0.0);
+
+        bots.try_append_weak(&[("  Spider ", 1.0)]).unwrap();
+        assert!(bots.is_bot("NiceSpider/2.0"));
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "http")]
+
+use http::{
+    header::{RETRY_AFTER, USER_AGENT},
+    HeaderMap, HeaderValue, StatusCode,
+};
+use isbot::{BotAction, BotCategory, BotPolicy, Bots};
+use std::time::Duration;
+
+fn headers(user_agent: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static(user_agent));
+    headers
+}
+
+#[test]
+fn test_rate_limited_and_blocked_bots() {
+    let policy = BotPolicy::new(Bots::default())
+        .rate_limit(BotCategory::Archiver, Duration::from_millis(1500));
+
+    let archiver = headers(
+        "Mozilla/5.0 (compatible; archive.org_bot +http://archive.org/details/archive.org_bot)",
+    );
+    let response = policy.response::<()>(&archiver).unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[RETRY_AFTER], "2");
+
+    let automation =
+        headers("Mozilla/5.0 (X11; Linux x86_64) HeadlessChrome/120.0.0.0 Safari/537.36");
+    assert_eq!(policy.action(&automation), BotAction::Block);
+    let response = policy.response::<()>(&automation).unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(response.headers().get(RETRY_AFTER).is_none());
+}
+
+#[test]
+fn test_allowed_requests() {
+    let policy = BotPolicy::new(Bots::default());
+    let browser =
+        headers("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0");
+    assert_eq!(policy.action(&browser), BotAction::Allow);
+    assert!(policy.response::<()>(&browser).is_none());
+    assert_eq!(policy.action(&HeaderMap::new()), BotAction::Allow);
+
+    let googlebot =
+        headers("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)");
+    assert_eq!(policy.action(&googlebot), BotAction::Allow);
+    let policy = policy.allow_good_bots(false);
+    assert_eq!(policy.action(&googlebot), BotAction::Block);
+}
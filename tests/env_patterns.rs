@@ -0,0 +1,36 @@
+use isbot::Bots;
+use std::env;
+
+// Setting environment variables while other threads read them is not thread safe, so this is the
+// only test in this binary
+#[test]
+fn test_patterns_from_env() {
+    let key = "ISBOT_TEST_APPEND_FROM_ENV";
+    let mut bots = Bots::empty();
+    assert!(bots.append_from_env(key, &[',']).is_err());
+
+    env::set_var(key, "CustomNewTestB0T, \"my bot, inc\"");
+    bots.append_from_env(key, &[',']).unwrap();
+    assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    assert!(bots.is_bot("My Bot, Inc/1.0"));
+    env::remove_var(key);
+
+    #[cfg(feature = "include-default-bots")]
+    {
+        let key = "ISBOT_TEST_FROM_ENV";
+        let default_len = Bots::default().len();
+        assert_eq!(Bots::from_env(key).len(), default_len);
+
+        env::set_var(key, "");
+        assert_eq!(Bots::from_env(key).len(), default_len);
+
+        env::set_var(key, "CustomNewTestB0T,^internal-monitor/\nfancyb0t");
+        let bots = Bots::from_env(key);
+        assert_eq!(bots.len(), default_len + 3);
+        assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+        assert!(bots.is_bot("Internal-Monitor/2.0"));
+        assert!(bots.is_bot("FancyB0T/1.0"));
+        assert!(bots.is_bot("Googlebot/2.1"));
+        env::remove_var(key);
+    }
+}
@@ -0,0 +1,34 @@
+use isbot::{Bots, BotsBuilder};
+
+// The override is process-global, so this is the only test in this binary
+#[test]
+fn test_global_default_override() {
+    let bot = "Mozilla/5.0 (CustomNewTestB0T /1.2)";
+    let before = Bots::default();
+    assert!(!before.is_bot(bot));
+    assert!(before.is_bot("Googlebot/2.1"));
+
+    isbot::set_global_default_patterns("customnewtestb0t\n^curl/").unwrap();
+    let overridden = Bots::default();
+    assert!(overridden.is_bot(bot));
+    assert!(overridden.is_bot("curl/7.64.1"));
+    assert!(!overridden.is_bot("Googlebot/2.1"));
+    assert!(!before.is_bot(bot));
+
+    let built = BotsBuilder::new().with_defaults().build().unwrap();
+    assert!(built.is_bot(bot));
+    assert!(!built.is_bot("Googlebot/2.1"));
+
+    let error = isbot::set_global_default_patterns("googlebot\n(invalid").unwrap_err();
+    assert!(matches!(
+        error,
+        isbot::BotsError::InvalidPattern { line: Some(2), .. }
+    ));
+    assert!(Bots::default().is_bot(bot));
+    assert!(!Bots::default().is_bot("Googlebot/2.1"));
+
+    isbot::reset_global_default();
+    let reset = Bots::default();
+    assert!(!reset.is_bot(bot));
+    assert!(reset.is_bot("Googlebot/2.1"));
+}
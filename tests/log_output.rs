@@ -0,0 +1,63 @@
+#![cfg(feature = "log")]
+
+use isbot::Bots;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+struct CapturingLogger {
+    messages: Mutex<Vec<String>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("isbot") {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    messages: Mutex::new(Vec::new()),
+};
+
+// The logger is process-global, so this is the only test in this binary
+#[test]
+fn test_debug_log_for_known_bot() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let bots = Bots::new("googlebot\nbot");
+    assert!(bots.is_bot("Googlebot/2.1 (+http://www.google.com/bot.html)"));
+    assert!(!bots.is_bot("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"));
+    assert_eq!(
+        bots.which_bot(&format!("Googlebot/{}", "x".repeat(200))),
+        Some("googlebot")
+    );
+
+    let messages = LOGGER.messages.lock().unwrap().clone();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[0],
+        "Bot user-agent \"Googlebot/2.1 (+http://www.google.com/bot.html)\" matched pattern 'googlebot'"
+    );
+    assert!(messages[1].contains(&format!("\"Googlebot/{}\"", "x".repeat(118))));
+
+    assert!(bots.is_bot("Googlebot/2.1\nBot user-agent 'forged'"));
+    let messages = LOGGER.messages.lock().unwrap().clone();
+    assert_eq!(messages.len(), 3);
+    assert!(messages[2].contains(r#""Googlebot/2.1\nBot user-agent 'forged'""#));
+    assert!(!messages[2].contains('\n'));
+
+    log::set_max_level(LevelFilter::Info);
+    assert!(bots.is_bot("Googlebot/2.1"));
+    assert_eq!(LOGGER.messages.lock().unwrap().len(), 3);
+}
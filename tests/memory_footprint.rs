@@ -0,0 +1,81 @@
+use isbot::Bots;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Upper bound on the heap retained by `Bots::default()`, documented in the README
+const MAX_DEFAULT_HEAP_BYTES: usize = 384 * 1024;
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Serializes the tests, since the allocator counts allocations from every thread
+static SERIAL: Mutex<()> = Mutex::new(());
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn default_heap_footprint() {
+    let _serial = SERIAL.lock().unwrap();
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    let bots = Bots::default();
+    let retained = ALLOCATED.load(Ordering::SeqCst) - before;
+    println!("Bots::default() retains {} heap bytes", retained);
+    assert!(
+        retained <= MAX_DEFAULT_HEAP_BYTES,
+        "Bots::default() retains {} heap bytes, exceeding the bound of {}",
+        retained,
+        MAX_DEFAULT_HEAP_BYTES
+    );
+    drop(bots);
+}
+
+#[test]
+fn browser_match_does_not_allocate() {
+    let _serial = SERIAL.lock().unwrap();
+    let bots = Bots::default();
+    let browser = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    // The first match on a thread allocates the regular expression search cache
+    assert!(!bots.is_bot(browser));
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for _ in 0..100 {
+        assert!(!bots.is_bot(browser));
+    }
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst) - before, 0);
+}
+
+#[test]
+fn long_browser_match_does_not_allocate() {
+    let _serial = SERIAL.lock().unwrap();
+    let bots = Bots::default();
+    let browser = format!(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 {}",
+        "Extension/1.0 ".repeat(20)
+    );
+    assert!(browser.len() > 256);
+    // The first long match on a thread also allocates the reused lowercase buffer
+    assert!(!bots.is_bot(&browser));
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for _ in 0..100 {
+        assert!(!bots.is_bot(&browser));
+    }
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst) - before, 0);
+}
@@ -10,7 +10,7 @@ fn test_fixture_bots() {
     let path = Path::new("fixtures").join("bots.txt");
     let file = File::open(&path).unwrap_or_else(|_| panic!("Unable to open file: {:?}", path));
     let reader = BufReader::new(file);
-    for user_agent in reader.lines().flatten() {
+    for user_agent in reader.lines().map_while(Result::ok) {
         assert!(
             bots.is_bot(&user_agent),
             "User-agent is not a bot: {}",
@@ -18,3 +18,8 @@ fn test_fixture_bots() {
         );
     }
 }
+
+#[test]
+fn test_default_bots_have_no_catch_all() {
+    assert!(!Bots::default().has_catch_all());
+}
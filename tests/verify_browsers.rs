@@ -10,7 +10,7 @@ fn test_fixture_browsers() {
     let path = Path::new("fixtures").join("browsers.txt");
     let file = File::open(&path).unwrap_or_else(|_| panic!("Unable to open file: {:?}", path));
     let reader = BufReader::new(file);
-    for user_agent in reader.lines().flatten() {
+    for user_agent in reader.lines().map_while(Result::ok) {
         assert!(
             !bots.is_bot(&user_agent),
             "User-agent is a bot, not a browser: {}",
@@ -0,0 +1,20 @@
+// Run in a WebAssembly runtime with `wasm-pack test --node --features wasm`
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use isbot::wasm::{is_bot, WasmBots};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_default_is_bot() {
+    assert!(is_bot("Googlebot"));
+    assert!(!is_bot("Opera/9.60 (Windows NT 6.0; U; en) Presto/2.1.1"));
+}
+
+#[wasm_bindgen_test]
+fn test_wrapped_bots() {
+    let mut bots = WasmBots::new();
+    bots.append(vec!["CustomNewTestB0T".to_string()]).unwrap();
+    assert!(bots.is_bot("Mozilla/5.0 (CustomNewTestB0T /1.2)"));
+    bots.remove(vec!["Chrome-Lighthouse".to_string()]);
+    assert!(!bots.is_bot("Chrome-Lighthouse"));
+}